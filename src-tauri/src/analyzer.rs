@@ -1,33 +1,21 @@
 use crate::models::*;
-use crate::parser::RustParser;
-use anyhow::{Context, Result};
-use cargo_metadata::MetadataCommand;
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use crate::tauri_commands::TauriCommands;
+
+pub const LARGE_MODULE_LINES: usize = 500;
+pub const HIGH_COUPLING_DEPS: usize = 10;
 
 pub struct ProjectAnalyzer {
-    root_path: PathBuf,
     modules: Vec<Module>,
-    dependencies: Vec<Dependency>,
     relationships: Vec<Relationship>,
 }
 
 impl ProjectAnalyzer {
-    pub fn new(root_path: PathBuf) -> Self {
+    pub fn new(structure: ProjectStructure) -> Self {
         Self {
-            root_path,
-            modules: Vec::new(),
-            dependencies: Vec::new(),
-            relationships: Vec::new(),
+            modules: structure.modules,
+            relationships: structure.relationships,
         }
     }
-
-    pub fn initialize_data(&mut self, structure: ProjectStructure) {
-        self.modules = structure.modules;
-        self.dependencies = structure.dependencies;
-        self.relationships = structure.relationships;
-    }
     
     pub fn detect_cycles(&self) -> Vec<Vec<String>> {
         let mut cycles = Vec::new();
@@ -83,21 +71,47 @@ impl ProjectAnalyzer {
                 .filter(|r| r.from == module.id)
                 .count();
             
-            let lines = std::fs::read_to_string(&module.path)
-                .map(|c| c.lines().count())
-                .unwrap_or(0);
+            let lines = module.lines_of_code.unwrap_or_else(|| Self::count_lines(module));
             
             metrics.insert(module.id.clone(), ModuleMetrics {
                 lines_of_code: lines,
                 incoming_deps: incoming,
                 outgoing_deps: outgoing,
                 complexity_score: module.items.len(),
+                churn: module.churn.clone(),
+                hotspot_score: 0.0,
             });
         }
+
+        // Change frequency times the mean of size and complexity, each
+        // relative to the project's largest value
+        let max = |value: fn(&ModuleMetrics) -> usize| metrics.values().map(value).max().unwrap_or(0).max(1) as f64;
+        let max_commits = max(|m| m.churn.as_ref().map_or(0, |c| c.commits));
+        let max_lines = max(|m| m.lines_of_code);
+        let max_complexity = max(|m| m.complexity_score);
+        for m in metrics.values_mut() {
+            let commits = m.churn.as_ref().map_or(0, |c| c.commits) as f64;
+            let size = (m.lines_of_code as f64 / max_lines + m.complexity_score as f64 / max_complexity) / 2.0;
+            m.hotspot_score = commits / max_commits * size;
+        }
         
         metrics
     }
     
+    pub fn count_lines(module: &Module) -> usize {
+        let read_lines = |path: &std::path::Path| {
+            std::fs::read_to_string(path)
+                .map(|c| c.lines().count())
+                .unwrap_or(0)
+        };
+
+        module
+            .source_files()
+            .into_iter()
+            .map(|path| read_lines(std::path::Path::new(path)))
+            .sum()
+    }
+    
     pub fn find_unused_modules(&self) -> Vec<String> {
         let mut unused = Vec::new();
         
@@ -115,182 +129,50 @@ impl ProjectAnalyzer {
         
         unused
     }
+}
 
+// (documented, total) public items of the module
+pub fn doc_coverage(module: &Module) -> (usize, usize) {
+    let public: Vec<&Item> = module
+        .items
+        .iter()
+        .filter(|i| i.visibility == Visibility::Public)
+        .collect();
+    let documented = public.iter().filter(|i| i.docs.is_some()).count();
+    (documented, public.len())
+}
 
+pub fn find_problems(structure: &ProjectStructure) -> ProjectProblems {
+    let analyzer = ProjectAnalyzer::new(structure.clone());
 
-
-    pub fn analyze(&mut self) -> Result<ProjectStructure> {
-        println!("Analyzing project at: {:?}", self.root_path);
-
-        // Parse Cargo.toml and dependencies
-        self.parse_dependencies()
-            .context("Failed to parse dependencies")?;
-
-        // Walk through source files
-        self.walk_source_files()
-            .context("Failed to walk source files")?;
-
-        // Build relationships
-        self.build_relationships();
-
-        Ok(ProjectStructure {
-            root_path: self.root_path.display().to_string(),
-            modules: self.modules.clone(),
-            dependencies: self.dependencies.clone(),
-            relationships: self.relationships.clone(),
-        })
-    }
-
-    fn parse_dependencies(&mut self) -> Result<()> {
-        let metadata = MetadataCommand::new()
-            .manifest_path(self.root_path.join("Cargo.toml"))
-            .exec()
-            .context("Failed to execute cargo metadata")?;
-
-        for package in &metadata.packages {
-            for dep in &package.dependencies {
-                let dep_type = match dep.kind {
-                    cargo_metadata::DependencyKind::Normal => DependencyType::Normal,
-                    cargo_metadata::DependencyKind::Development => DependencyType::Dev,
-                    cargo_metadata::DependencyKind::Build => DependencyType::Build,
-                    _ => DependencyType::Normal,
-                };
-
-                self.dependencies.push(Dependency {
-                    name: dep.name.clone(),
-                    version: dep.req.to_string(),
-                    dep_type,
-                });
-            }
-        }
-
-        Ok(())
-    }
-
-    fn walk_source_files(&mut self) -> Result<()> {
-        let src_dir = self.root_path.join("src");
-        if !src_dir.exists() {
-            return Ok(());
-        }
-
-        for entry in WalkDir::new(&src_dir)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("rs") {
-                let relative_path = path
-                    .strip_prefix(&self.root_path)
-                    .unwrap_or(path)
-                    .to_path_buf();
-
-                let module_path = self.path_to_module_name(&relative_path);
-
-                let mut parser = RustParser::new();
-                match parser.parse_file(path, &module_path) {
-                    Ok(module) => {
-                        let uses = parser.get_uses();
-                        let from_id = module.id.clone();
-
-                        self.modules.push(module);
-
-                        // Create relationships from use statements
-                        for use_path in uses {
-                            self.relationships.push(Relationship {
-                                from: from_id.clone(),
-                                to: use_path.replace("::", "_"),
-                                rel_type: RelationType::Uses,
-                            });
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse {}: {}", path.display(), e);
-                    }
-                }
+    let cycles = analyzer.detect_cycles();
+    let unused = analyzer.find_unused_modules();
+    let metrics = analyzer.calculate_metrics();
+    
+    let mut large_modules = Vec::new();
+    let mut highly_coupled = Vec::new();
+    
+    for (id, metric) in &metrics {
+        if metric.lines_of_code > LARGE_MODULE_LINES {
+            if let Some(module) = structure.modules.iter().find(|m| &m.id == id) {
+                large_modules.push(format!("{} ({} lines)", module.name, metric.lines_of_code));
             }
         }
-
-        // Check for tests, examples, benches
-        self.walk_additional_dirs("tests")?;
-        self.walk_additional_dirs("examples")?;
-        self.walk_additional_dirs("benches")?;
-
-        Ok(())
-    }
-
-    fn walk_additional_dirs(&mut self, dir_name: &str) -> Result<()> {
-        let dir = self.root_path.join(dir_name);
-        if !dir.exists() {
-            return Ok(());
-        }
-
-        for entry in WalkDir::new(&dir)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("rs") {
-                let relative_path = path
-                    .strip_prefix(&self.root_path)
-                    .unwrap_or(path)
-                    .to_path_buf();
-
-                let module_path = self.path_to_module_name(&relative_path);
-
-                let mut parser = RustParser::new();
-                match parser.parse_file(path, &module_path) {
-                    Ok(module) => {
-                        self.modules.push(module);
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse {}: {}", path.display(), e);
-                    }
-                }
+        if metric.incoming_deps > HIGH_COUPLING_DEPS {
+            if let Some(module) = structure.modules.iter().find(|m| &m.id == id) {
+                highly_coupled.push(format!("{} ({} deps)", module.name, metric.incoming_deps));
             }
         }
-
-        Ok(())
-    }
-
-    fn path_to_module_name(&self, path: &Path) -> String {
-        let path_str = path.to_string_lossy();
-        let path_str = path_str
-            .trim_start_matches("src/")
-            .trim_start_matches("tests/")
-            .trim_start_matches("examples/")
-            .trim_start_matches("benches/")
-            .trim_end_matches(".rs");
-
-        path_str
-            .replace("/mod", "")
-            .replace("/", "::")
-            .replace("\\", "::")
     }
 
-    fn build_relationships(&mut self) {
-        // Build parent-child relationships for modules
-        let mut module_map: HashMap<String, String> = HashMap::new();
-
-        for module in &self.modules {
-            module_map.insert(module.id.clone(), module.name.clone());
-        }
-
-        for module in &self.modules {
-            let parts: Vec<&str> = module.name.split("::").collect();
-            if parts.len() > 1 {
-                let parent_name = parts[..parts.len() - 1].join("::");
-                let parent_id = parent_name.replace("::", "_");
-
-                if module_map.contains_key(&parent_id) {
-                    self.relationships.push(Relationship {
-                        from: parent_id,
-                        to: module.id.clone(),
-                        rel_type: RelationType::Declares,
-                    });
-                }
-            }
-        }
+    let commands = TauriCommands::collect(structure);
+    
+    ProjectProblems {
+        cycles,
+        unused_modules: unused,
+        large_modules,
+        highly_coupled,
+        unregistered_commands: commands.unregistered(),
+        uninvoked_commands: commands.uninvoked(structure),
     }
 }
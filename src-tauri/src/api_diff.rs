@@ -0,0 +1,445 @@
+use crate::models::*;
+use quote::ToTokens;
+use std::collections::{BTreeMap, BTreeSet};
+
+// A public path together with the item it names
+struct ApiEntry<'a> {
+    public: &'a PublicPath,
+    item: Option<&'a Item>,
+}
+
+fn entries(structure: &ProjectStructure) -> BTreeMap<&str, ApiEntry<'_>> {
+    structure
+        .public_api
+        .iter()
+        .map(|public| (public.path.as_str(), ApiEntry { public, item: find_item(structure, public) }))
+        .collect()
+}
+
+fn find_item<'a>(structure: &'a ProjectStructure, public: &PublicPath) -> Option<&'a Item> {
+    let module = structure.modules.iter().find(|m| m.id == public.module_id)?;
+    match public.name.split_once("::") {
+        Some((self_type, method)) => module
+            .impls
+            .iter()
+            .filter(|b| b.self_type == self_type && b.trait_name.is_none())
+            .flat_map(|b| &b.methods)
+            .find(|m| m.name == method),
+        None => module
+            .items
+            .iter()
+            .find(|i| i.name == public.name && i.item_type == public.item_type),
+    }
+}
+
+// Compares the public API of two analyses of the same library. Removals and
+// incompatible changes are breaking; additions are not, except for required
+// trait members, variants of exhaustive enums and fields of structs that
+// could be built with a literal.
+pub fn diff(old: &ProjectStructure, new: &ProjectStructure) -> ApiDiff {
+    let old_entries = entries(old);
+    let new_entries = entries(new);
+    let paths: BTreeSet<&str> = old_entries.keys().chain(new_entries.keys()).copied().collect();
+
+    let mut changes = Vec::new();
+    for path in paths {
+        let change = match (old_entries.get(path), new_entries.get(path)) {
+            (Some(before), None) => ApiChange {
+                path: path.to_string(),
+                kind: ApiChangeKind::Removed,
+                item_type: before.public.item_type.clone(),
+                breaking: true,
+                reasons: vec![removal_reason(new, before)],
+                before: Some(describe(before)),
+                after: None,
+            },
+            (None, Some(after)) => ApiChange {
+                path: path.to_string(),
+                kind: ApiChangeKind::Added,
+                item_type: after.public.item_type.clone(),
+                breaking: false,
+                reasons: vec!["new public item".to_string()],
+                before: None,
+                after: Some(describe(after)),
+            },
+            (Some(before), Some(after)) => {
+                let differences = compare(before, after);
+                if differences.is_empty() {
+                    continue;
+                }
+                ApiChange {
+                    path: path.to_string(),
+                    kind: ApiChangeKind::Changed,
+                    item_type: after.public.item_type.clone(),
+                    breaking: differences.iter().any(|(_, breaking)| *breaking),
+                    reasons: differences.into_iter().map(|(reason, _)| reason).collect(),
+                    before: Some(describe(before)),
+                    after: Some(describe(after)),
+                }
+            }
+            (None, None) => continue,
+        };
+        changes.push(change);
+    }
+
+    let breaking = changes.iter().filter(|c| c.breaking).count();
+    let suggested_bump = if breaking > 0 {
+        "major"
+    } else if !changes.is_empty() {
+        "minor"
+    } else {
+        "patch"
+    };
+    ApiDiff {
+        changes,
+        breaking,
+        suggested_bump: suggested_bump.to_string(),
+    }
+}
+
+// Why a path disappeared: the item may still exist but be hidden now
+fn removal_reason(new: &ProjectStructure, before: &ApiEntry) -> String {
+    let still_defined = new
+        .modules
+        .iter()
+        .flat_map(|m| m.items.iter().map(move |i| (m, i)))
+        .find(|(m, i)| {
+            m.id == before.public.module_id && i.name == before.public.name && i.item_type == before.public.item_type
+        });
+    match still_defined {
+        Some((_, item)) if item.visibility != Visibility::Public => {
+            format!("visibility reduced to {:?}", item.visibility)
+        }
+        Some(_) => "no longer reachable from the crate root".to_string(),
+        None => "removed".to_string(),
+    }
+}
+
+fn describe(entry: &ApiEntry) -> String {
+    let Some(item) = entry.item else {
+        return format!("{:?} {}", entry.public.item_type, entry.public.path).to_lowercase();
+    };
+    match &item.signature {
+        Some(signature) if item.item_type == ItemType::Trait => {
+            return format!("{} {{ {} }}", signature, item.fields.join(" "));
+        }
+        Some(signature) => return signature.clone(),
+        None => {}
+    }
+    let mut keyword = format!("{:?}", item.item_type).to_lowercase();
+    if item.non_exhaustive {
+        keyword.insert_str(0, "#[non_exhaustive] ");
+    }
+    if item.fields.is_empty() {
+        format!("{} {}", keyword, entry.public.name)
+    } else {
+        format!("{} {} {{ {} }}", keyword, entry.public.name, item.fields.join(", "))
+    }
+}
+
+// Differences between two versions of one public path, each with whether it
+// breaks dependents
+fn compare(before: &ApiEntry, after: &ApiEntry) -> Vec<(String, bool)> {
+    if before.public.item_type != after.public.item_type {
+        return vec![(
+            format!("{:?} became {:?}", before.public.item_type, after.public.item_type).to_lowercase(),
+            true,
+        )];
+    }
+    let (Some(old), Some(new)) = (before.item, after.item) else {
+        return Vec::new();
+    };
+
+    match new.item_type {
+        ItemType::Function => match (&old.signature, &new.signature) {
+            (Some(old), Some(new)) if old != new => compare_signatures(old, new),
+            _ => Vec::new(),
+        },
+        ItemType::Struct => {
+            let mut differences = compare_exhaustiveness(old, new);
+            differences.extend(compare_fields(&old.fields, &new.fields, old.non_exhaustive));
+            differences
+        }
+        ItemType::Enum => {
+            let mut differences = compare_exhaustiveness(old, new);
+            differences.extend(compare_variants(&old.fields, &new.fields, old.non_exhaustive));
+            differences
+        }
+        ItemType::Trait => {
+            let mut differences = compare_declarations(old, new);
+            differences.extend(compare_trait_members(&old.fields, &new.fields));
+            differences
+        }
+        ItemType::Const | ItemType::Static | ItemType::Type => compare_declarations(old, new),
+        _ => Vec::new(),
+    }
+}
+
+// Trait headers (generics and supertraits), and the types of consts, statics
+// and aliases, which dependents rely on as written
+fn compare_declarations(old: &Item, new: &Item) -> Vec<(String, bool)> {
+    match (&old.signature, &new.signature) {
+        (Some(old), Some(new)) if old != new => vec![(format!("`{}` changed to `{}`", old, new), true)],
+        _ => Vec::new(),
+    }
+}
+
+// Matching on a `#[non_exhaustive]` type or building it requires a wildcard
+// or constructor outside its crate
+fn compare_exhaustiveness(old: &Item, new: &Item) -> Vec<(String, bool)> {
+    match (old.non_exhaustive, new.non_exhaustive) {
+        (false, true) => vec![("marked `#[non_exhaustive]`".to_string(), true)],
+        (true, false) => vec![("no longer `#[non_exhaustive]`".to_string(), false)],
+        _ => Vec::new(),
+    }
+}
+
+// `fn name`, `type Name` or `const NAME` for a member as stored by the parser
+fn member_key(member: &str) -> String {
+    let head = member.split(['(', '<', ':', '=', ';']).next().unwrap_or(member);
+    let mut words = head.split_whitespace().rev();
+    let name = words.next().unwrap_or_default();
+    format!("{} {}", words.next().unwrap_or_default(), name)
+}
+
+// A member's declaration, and whether it has a default implementers can rely on
+fn member_declaration(member: &str) -> (&str, bool) {
+    match member.strip_suffix(" { .. }").or_else(|| member.strip_suffix(" = ..;")) {
+        Some(declaration) => (declaration, true),
+        None => (member.trim_end_matches(';'), false),
+    }
+}
+
+fn compare_trait_members(old: &[String], new: &[String]) -> Vec<(String, bool)> {
+    let old_members: BTreeMap<String, (&str, bool)> =
+        old.iter().map(|m| (member_key(m), member_declaration(m))).collect();
+    let new_members: BTreeMap<String, (&str, bool)> =
+        new.iter().map(|m| (member_key(m), member_declaration(m))).collect();
+
+    let mut differences = Vec::new();
+    for (key, (declaration, had_default)) in &old_members {
+        match new_members.get(key) {
+            None => differences.push((format!("removed `{}`", key), true)),
+            Some((new_declaration, has_default)) => {
+                if new_declaration != declaration {
+                    differences.push((format!("`{}` changed to `{}`", declaration, new_declaration), true));
+                }
+                match (had_default, has_default) {
+                    (true, false) => differences.push((format!("`{}` no longer has a default", key), true)),
+                    (false, true) => differences.push((format!("`{}` now has a default", key), false)),
+                    _ => {}
+                }
+            }
+        }
+    }
+    for (key, (_, has_default)) in new_members.iter().filter(|(key, _)| !old_members.contains_key(*key)) {
+        // Implementations outside the crate must provide required members
+        match *has_default {
+            true => differences.push((format!("new provided `{}`", key), false)),
+            false => differences.push((format!("new required `{}`", key), true)),
+        }
+    }
+    differences
+}
+
+fn compare_signatures(old: &str, new: &str) -> Vec<(String, bool)> {
+    let (Some(old), Some(new)) = (parse_signature(old), parse_signature(new)) else {
+        return vec![("signature changed".to_string(), true)];
+    };
+
+    let mut differences = Vec::new();
+    let (old_bounds, new_bounds) = (trait_bounds(&old), trait_bounds(&new));
+    for bound in new_bounds.difference(&old_bounds) {
+        differences.push((format!("new trait bound `{}`", bound), true));
+    }
+    for bound in old_bounds.difference(&new_bounds) {
+        differences.push((format!("removed trait bound `{}`", bound), false));
+    }
+
+    let (old, new) = (without_bounds(old), without_bounds(new));
+    if tokens(&old.inputs) != tokens(&new.inputs) {
+        differences.push(("parameters changed".to_string(), true));
+    }
+    if tokens(&old.output) != tokens(&new.output) {
+        differences.push(("return type changed".to_string(), true));
+    }
+    if tokens(&old.generics) != tokens(&new.generics) {
+        differences.push(("generic parameters changed".to_string(), true));
+    }
+    if old.asyncness.is_some() != new.asyncness.is_some()
+        || old.unsafety.is_some() != new.unsafety.is_some()
+        || old.constness.is_some() != new.constness.is_some()
+    {
+        differences.push(("qualifiers changed (async, unsafe or const)".to_string(), true));
+    }
+    differences
+}
+
+// Signatures are stored as `pub fn name<T>(..) -> R`, which parses as a
+// foreign function once terminated
+fn parse_signature(signature: &str) -> Option<syn::Signature> {
+    syn::parse_str::<syn::ForeignItemFn>(&format!("{};", signature))
+        .ok()
+        .map(|f| f.sig)
+}
+
+// `T: Clone` for every bound on a generic parameter or in the where clause
+fn trait_bounds(sig: &syn::Signature) -> BTreeSet<String> {
+    let mut bounds = BTreeSet::new();
+    for param in sig.generics.type_params() {
+        for bound in &param.bounds {
+            bounds.insert(format!("{}: {}", param.ident, tokens(bound)));
+        }
+    }
+    if let Some(where_clause) = &sig.generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let syn::WherePredicate::Type(predicate) = predicate {
+                for bound in &predicate.bounds {
+                    bounds.insert(format!("{}: {}", tokens(&predicate.bounded_ty), tokens(bound)));
+                }
+            }
+        }
+    }
+    bounds
+}
+
+fn without_bounds(mut sig: syn::Signature) -> syn::Signature {
+    for param in sig.generics.type_params_mut() {
+        param.bounds.clear();
+        param.colon_token = None;
+    }
+    sig.generics.where_clause = None;
+    sig
+}
+
+// Token text without the spaces `to_string` puts between every token
+fn tokens<T: ToTokens>(node: &T) -> String {
+    let text = node.to_token_stream().to_string();
+    let mut compact = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != ' ' {
+            compact.push(c);
+        } else if compact.ends_with(|p: char| p.is_alphanumeric() || p == '_')
+            && chars.peek().is_some_and(|n| n.is_alphanumeric() || *n == '_' || *n == '\'')
+        {
+            // Keep the space in `dyn Trait` or `impl Fn`
+            compact.push(' ');
+        }
+    }
+    compact.replace(',', ", ")
+}
+
+// `pub name: Type` -> ("name", true); tuple fields are keyed by position
+fn field_key(index: usize, field: &str) -> (String, bool) {
+    let public = field.starts_with("pub ");
+    let rest = if public { &field[4..] } else { field };
+    let key = match rest.split_once(':') {
+        Some((name, _)) if !name.contains(['(', '<', '&']) => name.trim().to_string(),
+        _ => index.to_string(),
+    };
+    (key, public)
+}
+
+fn compare_fields(old: &[String], new: &[String], non_exhaustive: bool) -> Vec<(String, bool)> {
+    let old_fields: BTreeMap<(String, bool), &String> =
+        old.iter().enumerate().map(|(i, f)| (field_key(i, f), f)).collect();
+    let new_fields: BTreeMap<(String, bool), &String> =
+        new.iter().enumerate().map(|(i, f)| (field_key(i, f), f)).collect();
+    // Structs without private fields can be built (and destructured) outside
+    // the crate, so any new field breaks those uses
+    let constructible = !non_exhaustive && old_fields.keys().all(|(_, public)| *public);
+
+    let mut differences = Vec::new();
+    for ((name, public), field) in &old_fields {
+        if !public {
+            continue;
+        }
+        match new_fields.get(&(name.clone(), true)) {
+            None => differences.push((format!("removed public field `{}`", name), true)),
+            Some(new_field) if new_field != field => {
+                differences.push((format!("field `{}` changed from `{}` to `{}`", name, field, new_field), true))
+            }
+            _ => {}
+        }
+    }
+    for (name, public) in new_fields.keys() {
+        if old_fields.contains_key(&(name.clone(), *public)) {
+            continue;
+        }
+        if *public {
+            differences.push((format!("new public field `{}`", name), constructible));
+        } else if constructible && !old_fields.contains_key(&(name.clone(), true)) {
+            differences.push((format!("new private field `{}` prevents construction outside the crate", name), true));
+        }
+    }
+    differences
+}
+
+fn variant_name(variant: &str) -> &str {
+    variant
+        .split(|c: char| c == '(' || c == '{' || c == '=' || c.is_whitespace())
+        .next()
+        .unwrap_or(variant)
+}
+
+fn compare_variants(old: &[String], new: &[String], non_exhaustive: bool) -> Vec<(String, bool)> {
+    let old_variants: BTreeMap<&str, &String> = old.iter().map(|v| (variant_name(v), v)).collect();
+    let new_variants: BTreeMap<&str, &String> = new.iter().map(|v| (variant_name(v), v)).collect();
+
+    let mut differences = Vec::new();
+    for (name, variant) in &old_variants {
+        match new_variants.get(name) {
+            None => differences.push((format!("removed variant `{}`", name), true)),
+            Some(new_variant) if new_variant != variant => {
+                differences.push((format!("variant `{}` changed to `{}`", variant, new_variant), true))
+            }
+            _ => {}
+        }
+    }
+    for name in new_variants.keys().filter(|name| !old_variants.contains_key(*name)) {
+        // Exhaustive matches on the enum stop compiling
+        differences.push((format!("new variant `{}`", name), !non_exhaustive));
+    }
+    differences
+}
+
+// Plain-text report grouped into breaking and non-breaking changes
+pub fn render_text(diff: &ApiDiff) -> String {
+    let mut text = format!(
+        "Public API changes: {} breaking, {} non-breaking (suggested version bump: {})\n",
+        diff.breaking,
+        diff.changes.len() - diff.breaking,
+        diff.suggested_bump
+    );
+    for (title, breaking) in [("Breaking", true), ("Non-breaking", false)] {
+        let changes: Vec<&ApiChange> = diff.changes.iter().filter(|c| c.breaking == breaking).collect();
+        if changes.is_empty() {
+            continue;
+        }
+        text.push_str(&format!("\n{}:\n", title));
+        for change in changes {
+            let marker = match change.kind {
+                ApiChangeKind::Added => '+',
+                ApiChangeKind::Removed => '-',
+                ApiChangeKind::Changed => '~',
+            };
+            text.push_str(&format!(
+                "  {} {:?} {}: {}\n",
+                marker,
+                change.item_type,
+                change.path,
+                change.reasons.join("; ")
+            ));
+            if change.kind == ApiChangeKind::Changed {
+                for (label, value) in [("before:", &change.before), ("after:", &change.after)] {
+                    if let Some(value) = value {
+                        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+                        text.push_str(&format!("      {:<8}{}\n", label, value));
+                    }
+                }
+            }
+        }
+    }
+    text
+}
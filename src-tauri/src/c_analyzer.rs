@@ -0,0 +1,490 @@
+use crate::language::{FileState, LanguageAnalyzer, ParsedFile};
+use crate::models::*;
+use crate::walker::SourceWalker;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+const SOURCE_EXTENSIONS: &[&str] = &["c", "h", "cc", "cpp", "cxx", "c++", "hh", "hpp", "hxx", "h++", "inl"];
+
+// Where CMake, Meson and Bear usually leave the compilation database
+const COMPILE_COMMANDS: &[&str] = &["compile_commands.json", "build/compile_commands.json"];
+
+// Build files at the root that mark a C/C++ project without walking the tree
+const BUILD_FILES: &[&str] = &["CMakeLists.txt", "Makefile", "makefile", "GNUmakefile", "meson.build"];
+
+const KEYWORDS: &[&str] = &["if", "for", "while", "switch", "return", "sizeof", "else", "do", "case"];
+
+// Treats every C/C++ file as a module and `#include` directives as `Uses`
+// edges, resolved the way the compiler would see them
+pub struct CAnalyzer {
+    root_path: PathBuf,
+    canonical_root: PathBuf,
+    options: AnalysisOptions,
+    // source file -> include directories from its compile command
+    include_dirs: HashMap<PathBuf, Vec<PathBuf>>,
+    // union of all include directories, used for headers and files the
+    // compilation database doesn't mention
+    all_include_dirs: Vec<PathBuf>,
+    files: Vec<PathBuf>,
+}
+
+impl CAnalyzer {
+    pub fn new(root_path: PathBuf, options: AnalysisOptions) -> Self {
+        Self {
+            canonical_root: canonical(&root_path),
+            root_path,
+            options,
+            include_dirs: HashMap::new(),
+            all_include_dirs: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    fn compile_commands_path(&self) -> Option<PathBuf> {
+        COMPILE_COMMANDS
+            .iter()
+            .map(|p| self.root_path.join(p))
+            .find(|p| p.is_file())
+    }
+
+    fn load_compile_commands(&mut self, path: &Path) -> Result<()> {
+        let content = fs::read_to_string(path).context("Failed to read compile_commands.json")?;
+        let entries: Vec<serde_json::Value> =
+            serde_json::from_str(&content).context("Failed to parse compile_commands.json")?;
+
+        for entry in entries {
+            let directory = PathBuf::from(entry.get("directory").and_then(|v| v.as_str()).unwrap_or("."));
+            let Some(file) = entry.get("file").and_then(|v| v.as_str()) else {
+                continue;
+            };
+
+            let arguments: Vec<String> = match (entry.get("arguments"), entry.get("command")) {
+                (Some(args), _) => args
+                    .as_array()
+                    .map(|a| a.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect())
+                    .unwrap_or_default(),
+                (None, Some(command)) => split_command(command.as_str().unwrap_or("")),
+                (None, None) => Vec::new(),
+            };
+
+            let dirs: Vec<PathBuf> = include_flags(&arguments)
+                .into_iter()
+                .map(|dir| canonical(&directory.join(dir)))
+                .collect();
+            for dir in &dirs {
+                if !self.all_include_dirs.contains(dir) {
+                    self.all_include_dirs.push(dir.clone());
+                }
+            }
+            self.include_dirs.insert(canonical(&directory.join(file)), dirs);
+        }
+
+        Ok(())
+    }
+
+    fn module_name(&self, path: &Path) -> String {
+        path.strip_prefix(&self.canonical_root)
+            .or_else(|_| path.strip_prefix(&self.root_path))
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    fn module_id(name: &str) -> String {
+        name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    fn is_header(path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.starts_with('h') || e == "inl")
+    }
+
+    fn language_of(path: &Path) -> Language {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("c") | Some("h") => Language::C,
+            _ => Language::Cpp,
+        }
+    }
+
+    fn resolve_include(&self, from_file: &Path, include: &str, quoted: bool) -> Option<PathBuf> {
+        // `"..."` includes look next to the including file first
+        if quoted {
+            if let Some(found) = from_file.parent().map(|dir| canonical(&dir.join(include))) {
+                if found.is_file() {
+                    return Some(found);
+                }
+            }
+        }
+
+        let dirs = self
+            .include_dirs
+            .get(&canonical(from_file))
+            .unwrap_or(&self.all_include_dirs);
+        for dir in dirs {
+            let found = canonical(&dir.join(include));
+            if found.is_file() {
+                return Some(found);
+            }
+        }
+
+        // Without a compilation database, fall back to a unique project file
+        // whose path ends with the included one
+        if self.include_dirs.is_empty() {
+            let suffix = format!("/{}", include.trim_start_matches("./"));
+            let mut matches = self.files.iter().filter(|f| f.to_string_lossy().ends_with(&suffix));
+            if let (Some(found), None) = (matches.next(), matches.next()) {
+                return Some(found.clone());
+            }
+        }
+
+        None
+    }
+
+    fn extract_includes(content: &str) -> Vec<(String, bool)> {
+        static INCLUDE: OnceLock<Regex> = OnceLock::new();
+        let include = INCLUDE.get_or_init(|| {
+            Regex::new(r#"(?m)^\s*#\s*include\s*(?:"([^"]+)"|<([^>]+)>)"#).expect("valid include regex")
+        });
+
+        include
+            .captures_iter(content)
+            .filter_map(|caps| match (caps.get(1), caps.get(2)) {
+                (Some(quoted), _) => Some((quoted.as_str().to_string(), true)),
+                (None, Some(angled)) => Some((angled.as_str().to_string(), false)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn extract_items(content: &str, is_header: bool) -> Vec<Item> {
+        static MACRO: OnceLock<Regex> = OnceLock::new();
+        static RECORD: OnceLock<Regex> = OnceLock::new();
+        static TYPEDEF: OnceLock<Regex> = OnceLock::new();
+        static FUNCTION: OnceLock<Regex> = OnceLock::new();
+        let macro_def = MACRO.get_or_init(|| Regex::new(r"(?m)^\s*#\s*define\s+(\w+)").expect("valid define regex"));
+        let record = RECORD.get_or_init(|| {
+            Regex::new(r"(?m)^(typedef\s+)?(struct|union|enum|class)\s+(?:class\s+)?(\w+)(?:\s+final)?\s*(?::\s*([^{;]+))?\{")
+                .expect("valid record regex")
+        });
+        let typedef = TYPEDEF.get_or_init(|| {
+            Regex::new(r"(?m)^(?:typedef\s+[^;{]*?|\}[ \t]*)(\w+)\s*;|^using\s+(\w+)\s*=").expect("valid typedef regex")
+        });
+        let function = FUNCTION.get_or_init(|| {
+            Regex::new(r"(?m)^((?:[A-Za-z_][\w:<>,]*[\s\*&]+)*)([A-Za-z_][\w]*(?:::~?\w+)*)\s*\([^;{}()]*(?:\([^()]*\)[^;{}()]*)*\)\s*(?:const\s*)?(?:noexcept\s*)?(?:override\s*)?(;|\{)")
+                .expect("valid function regex")
+        });
+
+        let exported = |public: bool| if public { Visibility::Public } else { Visibility::Private };
+        let mut found: Vec<(usize, Item)> = Vec::new();
+        let mut push = |offset: usize, name: &str, item_type: ItemType, visibility: Visibility, bases: Vec<String>| {
+            if !found.iter().any(|(_, i)| i.name == name && i.item_type == item_type) {
+                found.push((
+                    offset,
+                    Item {
+                        name: name.to_string(),
+                        item_type,
+                        visibility,
+                        bases,
+                        binding: None,
+                        span: None,
+                        docs: None,
+                        signature: None,
+                        fields: Vec::new(),
+                        effective_visibility: None,
+                        non_exhaustive: false,
+                    },
+                ));
+            }
+        };
+
+        for caps in macro_def.captures_iter(content) {
+            // Include guards are not part of the interface
+            let name = &caps[1];
+            if name.ends_with("_H") || name.ends_with("_H_") || name.ends_with("_HPP") {
+                continue;
+            }
+            push(caps.get(0).map_or(0, |m| m.start()), name, ItemType::Macro, exported(is_header), Vec::new());
+        }
+
+        for caps in record.captures_iter(content) {
+            let item_type = if &caps[2] == "enum" { ItemType::Enum } else { ItemType::Struct };
+            let bases = caps
+                .get(4)
+                .map(|b| {
+                    b.as_str()
+                        .split(',')
+                        .filter_map(|base| base.split_whitespace().last())
+                        .map(|base| base.to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+            push(caps.get(0).map_or(0, |m| m.start()), &caps[3], item_type, exported(is_header), bases);
+        }
+
+        for caps in typedef.captures_iter(content) {
+            if let Some(name) = caps.get(1).or(caps.get(2)) {
+                push(name.start(), name.as_str(), ItemType::Type, exported(is_header), Vec::new());
+            }
+        }
+
+        for caps in function.captures_iter(content) {
+            let prefix = caps[1].trim();
+            let name = &caps[2];
+            let is_definition = &caps[3] == "{";
+            let first_word = prefix.split_whitespace().next().unwrap_or("");
+            if KEYWORDS.contains(&name)
+                || matches!(first_word, "typedef" | "return" | "else" | "struct" | "enum" | "union" | "class")
+                || (prefix.is_empty() && !is_definition)
+                // `public:` and labels are not part of a return type
+                || prefix.split_whitespace().any(|w| w.ends_with(':'))
+            {
+                continue;
+            }
+            // `static` functions have internal linkage
+            let public = !prefix.split_whitespace().any(|w| w == "static");
+            push(caps.get(0).map_or(0, |m| m.start()), name, ItemType::Function, exported(public), Vec::new());
+        }
+
+        found.sort_by_key(|(offset, _)| *offset);
+        found.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+impl LanguageAnalyzer for CAnalyzer {
+    fn language(&self) -> Language {
+        Language::C
+    }
+
+    fn extensions(&self) -> &[&str] {
+        SOURCE_EXTENSIONS
+    }
+
+    fn detect(&self) -> bool {
+        if self.compile_commands_path().is_some() || BUILD_FILES.iter().any(|f| self.root_path.join(f).is_file()) {
+            return true;
+        }
+        let mut walker = SourceWalker::new(&self.root_path, SOURCE_EXTENSIONS, &self.options);
+        walker.has_files(&self.root_path)
+    }
+
+    fn parse_dependencies(&mut self) -> Result<Vec<Dependency>> {
+        // C has no manifest; the compilation database only tells us how to
+        // find headers
+        if let Some(path) = self.compile_commands_path() {
+            self.load_compile_commands(&path)?;
+        }
+        Ok(Vec::new())
+    }
+
+    fn discover_files(&mut self, walker: &mut SourceWalker) -> Vec<PathBuf> {
+        self.files = walker.files(&self.root_path).iter().map(|f| canonical(f)).collect();
+        self.files.clone()
+    }
+
+    fn parse_file(&mut self, path: &Path) -> Result<ParsedFile> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        let content = strip_comments(&content);
+
+        let name = self.module_name(path);
+        let id = Self::module_id(&name);
+        let is_header = Self::is_header(path);
+
+        let mut relationships: Vec<Relationship> = Vec::new();
+        for (include, quoted) in Self::extract_includes(&content) {
+            // System and third-party headers outside the project are not modules
+            let Some(found) = self.resolve_include(path, &include, quoted) else {
+                continue;
+            };
+            if !self.files.contains(&found) {
+                continue;
+            }
+            let to = Self::module_id(&self.module_name(&found));
+            if to != id && !relationships.iter().any(|r| r.to == to) {
+                relationships.push(Relationship {
+                    from: id.clone(),
+                    to,
+                    rel_type: RelationType::Uses,
+                    label: Some(include),
+                });
+            }
+        }
+
+        let items = Self::extract_items(&content, is_header);
+        let path_str = name.to_lowercase();
+        let module_type = if path_str.starts_with("test") || path_str.contains("/test") {
+            ModuleType::Test
+        } else if path_str.contains("example") {
+            ModuleType::Example
+        } else if items.iter().any(|i| i.name == "main" && i.item_type == ItemType::Function) {
+            ModuleType::Binary
+        } else {
+            ModuleType::Module
+        };
+
+        let module = Module {
+            id,
+            name,
+            path: path.display().to_string(),
+            module_type,
+            // Headers are the interface other files can include
+            visibility: if is_header { Visibility::Public } else { Visibility::Private },
+            items,
+            entry_points: Vec::new(),
+            language: Self::language_of(path),
+            invokes: Vec::new(),
+            impls: Vec::new(),
+            package: None,
+            docs: None,
+            reexports: Vec::new(),
+            lines_of_code: None,
+            churn: None,
+            owners: Vec::new(),
+            primary_authors: Vec::new(),
+            files: Vec::new(),
+        };
+
+        Ok(ParsedFile {
+            module,
+            relationships,
+            nested_modules: Vec::new(),
+            state: FileState::default(),
+        })
+    }
+
+    fn build_relationships(&mut self, _modules: &[Module]) -> Vec<Relationship> {
+        // Files don't nest; everything is expressed through includes
+        Vec::new()
+    }
+}
+
+// `-Idir`, `-I dir`, `-isystem dir`, `-iquote dir`, and `/I dir` when the
+// compiler is MSVC's, where a leading slash can't be an absolute path
+fn include_flags(arguments: &[String]) -> Vec<String> {
+    let compiler = arguments
+        .iter()
+        .map(|arg| Path::new(arg).file_stem().and_then(|s| s.to_str()).unwrap_or("").to_lowercase())
+        .find(|name| name != "ccache" && name != "sccache")
+        .unwrap_or_default();
+    let flags: &[&str] = match compiler.as_str() {
+        "cl" | "clang-cl" => &["-I", "-isystem", "-iquote", "-idirafter", "/I"],
+        _ => &["-I", "-isystem", "-iquote", "-idirafter"],
+    };
+
+    let mut dirs = Vec::new();
+    let mut args = arguments.iter().skip(1);
+    while let Some(arg) = args.next() {
+        for flag in flags {
+            if arg == flag {
+                if let Some(dir) = args.next() {
+                    dirs.push(dir.clone());
+                }
+                break;
+            }
+            if let Some(dir) = arg.strip_prefix(flag) {
+                dirs.push(dir.to_string());
+                break;
+            }
+        }
+    }
+    dirs
+}
+
+// Shell-style splitting of a `command` string, honoring quotes and escapes
+fn split_command(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', _) => current.extend(chars.next()),
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            (c, _) => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+// Compile commands mix absolute and relative paths, sometimes through
+// symlinks; compare them in one canonical form
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| {
+        let mut normalized = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                other => normalized.push(other.as_os_str()),
+            }
+        }
+        normalized
+    })
+}
+
+// Drops `/* */` and `//` comments while keeping line structure and string literals
+fn strip_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(quote) = in_string {
+            out.push(c);
+            if c == '\\' {
+                out.extend(chars.next());
+            } else if c == quote || c == '\n' {
+                in_string = None;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"' | '\'', _) => {
+                in_string = Some(c);
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,126 @@
+use crate::models::*;
+use anyhow::Result;
+use git2::{Delta, DiffOptions, Patch, Repository, Sort};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+pub const DEFAULT_CHURN_DAYS: u32 = 90;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+// Fills in each module's churn from the commits of the last
+// `options.churn_days` days, if set. Projects outside a git repository get
+// none.
+pub fn link_churn(structure: &mut ProjectStructure, root_path: &Path, options: &AnalysisOptions) {
+    let Some(days) = options.churn_days.filter(|days| *days > 0) else {
+        return;
+    };
+    let Ok(repo) = Repository::discover(root_path) else {
+        return;
+    };
+
+    match collect(&repo, root_path, &structure.modules, days) {
+        Ok(churn) => {
+            for (module, churn) in structure.modules.iter_mut().zip(churn) {
+                module.churn = Some(churn);
+            }
+        }
+        Err(e) => eprintln!("Failed to compute churn: {:#}", e),
+    }
+}
+
+// Per-module totals, in the order of `modules`
+fn collect(repo: &Repository, root_path: &Path, modules: &[Module], days: u32) -> Result<Vec<Churn>> {
+    let Some(workdir) = repo.workdir() else {
+        return Ok(Vec::new());
+    };
+    let workdir = workdir.canonicalize()?;
+    let relative = |path: &Path| -> Option<String> {
+        let path = path.canonicalize().ok()?;
+        Some(path.strip_prefix(&workdir).ok()?.to_string_lossy().replace('\\', "/"))
+    };
+
+    // Repository paths of module files, and of the directories of modules
+    // spanning several files (Go packages), whose files come and go
+    let mut files = HashMap::new();
+    let mut dirs = HashMap::new();
+    for (index, module) in modules.iter().enumerate() {
+        let path = Path::new(&module.path);
+        let (key, map) = match module.files.is_empty() {
+            true => (relative(path), &mut files),
+            false => (path.parent().and_then(relative), &mut dirs),
+        };
+        // Inline modules share their file with the module defining them,
+        // which comes first and takes the file's churn
+        if let Some(key) = key {
+            map.entry(key).or_insert(index);
+        }
+    }
+
+    let mut diff_options = DiffOptions::new();
+    if let Some(prefix) = relative(root_path).filter(|p| !p.is_empty()) {
+        diff_options.pathspec(prefix);
+    }
+
+    let since = chrono::Utc::now().timestamp() - days as i64 * SECONDS_PER_DAY;
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+    walk.set_sorting(Sort::TIME)?;
+
+    let mut churn = vec![Churn { window_days: days, ..Churn::default() }; modules.len()];
+    let mut authors: Vec<HashSet<String>> = vec![HashSet::new(); modules.len()];
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.time().seconds() < since {
+            break;
+        }
+        // A merge repeats the changes of the branch it brings in
+        if commit.parent_count() > 1 {
+            continue;
+        }
+
+        let parent = commit.parents().next().map(|p| p.tree()).transpose()?;
+        let mut diff = repo.diff_tree_to_tree(parent.as_ref(), Some(&commit.tree()?), Some(&mut diff_options))?;
+        diff.find_similar(None)?;
+
+        let mut touched = HashSet::new();
+        for (index, delta) in diff.deltas().enumerate() {
+            let Some(path) = delta.new_file().path().map(|p| p.to_string_lossy().replace('\\', "/")) else {
+                continue;
+            };
+            let module = files.get(&path).copied().or_else(|| {
+                let (dir, name) = path.rsplit_once('/').unwrap_or(("", &path));
+                dirs.get(dir).filter(|_| name.ends_with(".go")).copied()
+            });
+            let Some(module) = module else {
+                continue;
+            };
+
+            // History is walked backwards, so older changes to a renamed
+            // file are found under its previous name
+            if delta.status() == Delta::Renamed {
+                if let Some(old) = delta.old_file().path() {
+                    files.insert(old.to_string_lossy().replace('\\', "/"), module);
+                }
+            }
+
+            if let Some(patch) = Patch::from_diff(&diff, index)? {
+                let (_, added, removed) = patch.line_stats()?;
+                churn[module].lines_added += added;
+                churn[module].lines_removed += removed;
+            }
+            touched.insert(module);
+        }
+
+        let author = commit.author().name().unwrap_or_default().to_string();
+        for module in touched {
+            churn[module].commits += 1;
+            authors[module].insert(author.clone());
+        }
+    }
+
+    for (churn, authors) in churn.iter_mut().zip(authors) {
+        churn.authors = authors.len();
+    }
+    Ok(churn)
+}
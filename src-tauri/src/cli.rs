@@ -0,0 +1,501 @@
+use crate::analyzer::ProjectAnalyzer;
+use crate::api_diff;
+use crate::churn::DEFAULT_CHURN_DAYS;
+use crate::export;
+use crate::git_history;
+use crate::html_docs;
+use crate::language;
+use crate::models::*;
+use crate::ownership;
+use crate::snapshot;
+use crate::structure_diff;
+use crate::trend;
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+const USAGE: &str = "Usage:
+  rust-project-visualizer                      start the desktop app
+  rust-project-visualizer export <path> [options]
+  rust-project-visualizer docs <path> --output <dir> [--include-ignored]
+  rust-project-visualizer api <path> [--include-ignored]
+  rust-project-visualizer snapshot <path> --output <file> [--rev <revision>] [--include-ignored]
+  rust-project-visualizer hotspots [<path>] [--days <n>] [--limit <n>] [--include-ignored]
+  rust-project-visualizer owners [<path>] [--blame] [--json] [--include-ignored]
+  rust-project-visualizer history [<path>] [--limit <n>] [--rev <revision>]
+  rust-project-visualizer trend [<path>] [--limit <n>] [--step <k>] [--rev <revision>]
+                                [--format csv|json] [--output <file>] [--include-ignored]
+  rust-project-visualizer api-diff <old> <new> [--json] [--include-ignored]
+  rust-project-visualizer diff <old> <new> [--json] [--include-ignored]
+
+Export options:
+  --format <format>       dot, graphml, gexf, plantuml-component,
+                          plantuml-class or structurizr (default: dot)
+  --root <module>         only export this module and its submodules
+  --depth <n>             levels below the root to include
+  --output <file>         write to a file instead of stdout
+  --include-ignored       also analyze ignored and excluded files
+
+api-diff compares the public API of two versions of a library; diff compares
+their whole architecture (printed as Markdown unless --json is given). <old> and
+<new> are project directories, snapshot files or <path>@<revision> for the
+project at a git commit, tag or branch, e.g. `diff .@v1.0 .`.
+
+history lists recent commits of the repository containing <path>;
+snapshot --rev analyzes a commit without touching the working tree. trend
+analyzes every <k>-th of the last commits (20 by default) and prints module
+count, coupling, cycles and problem counts per commit, oldest first. Rust
+and Python files unchanged since the previous sampled commit are not parsed
+again.
+
+hotspots ranks modules by how often they changed in the last <n> days (90
+by default) relative to their size and complexity.
+
+owners lists the teams from CODEOWNERS with their modules, dependencies that
+cross team boundaries and modules nobody owns; --blame adds each module's
+primary authors from git blame.";
+
+// Runs a command-line invocation. Returns None when no subcommand was given
+// and the desktop app should start instead.
+pub fn run(args: &[String]) -> Option<i32> {
+    let command = args.get(1)?;
+    let result = match command.as_str() {
+        "export" => export_command(&args[2..]),
+        "docs" => docs_command(&args[2..]),
+        "api" => api_command(&args[2..]),
+        "snapshot" => snapshot_command(&args[2..]),
+        "hotspots" => hotspots_command(&args[2..]),
+        "owners" => owners_command(&args[2..]),
+        "history" => history_command(&args[2..]),
+        "trend" => trend_command(&args[2..]),
+        "api-diff" => diff_command(&args[2..], true),
+        "diff" => diff_command(&args[2..], false),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        // Platform launchers may pass their own flags (e.g. `-psn_*` on macOS)
+        _ if command.starts_with('-') => return None,
+        other => Err(anyhow::anyhow!("Unknown command: {}\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            Some(1)
+        }
+    }
+}
+
+fn export_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut output = None;
+    let mut analysis = AnalysisOptions::default();
+    let mut options = ExportOptions::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .with_context(|| format!("{} requires a value", name))
+        };
+        match arg.as_str() {
+            "--format" => options.format = parse_format(&value("--format")?)?,
+            "--root" => options.root = Some(value("--root")?),
+            "--depth" => {
+                let depth = value("--depth")?;
+                options.depth = Some(depth.parse().with_context(|| format!("Invalid depth: {}", depth))?);
+            }
+            "--output" | "-o" => output = Some(PathBuf::from(value("--output")?)),
+            "--include-ignored" => analysis.include_ignored = true,
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let path = path.with_context(|| format!("Missing project path\n\n{}", USAGE))?;
+    if !path.exists() {
+        bail!("Project path does not exist: {}", path.display());
+    }
+
+    let structure = language::analyze_project(&path, &analysis)?;
+    let rendered = export::export(&structure, &options);
+
+    match output {
+        Some(file) => fs::write(&file, rendered)
+            .with_context(|| format!("Failed to write {}", file.display()))?,
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+fn docs_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut output = None;
+    let mut analysis = AnalysisOptions::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" | "-o" => {
+                output = Some(PathBuf::from(args.next().context("--output requires a value")?))
+            }
+            "--include-ignored" => analysis.include_ignored = true,
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let path = path.with_context(|| format!("Missing project path\n\n{}", USAGE))?;
+    let output = output.with_context(|| format!("Missing --output directory\n\n{}", USAGE))?;
+    if !path.exists() {
+        bail!("Project path does not exist: {}", path.display());
+    }
+
+    let structure = language::analyze_project(&path, &analysis)?;
+    let index = html_docs::generate(&structure, &output)?;
+    eprintln!("Documentation written to {}", index.display());
+    Ok(())
+}
+
+fn snapshot_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut output = None;
+    let mut revision = None;
+    let mut analysis = AnalysisOptions::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" | "-o" => {
+                output = Some(PathBuf::from(args.next().context("--output requires a value")?))
+            }
+            "--rev" => revision = Some(args.next().context("--rev requires a value")?),
+            "--include-ignored" => analysis.include_ignored = true,
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let path = path.with_context(|| format!("Missing project path\n\n{}", USAGE))?;
+    let output = output.with_context(|| format!("Missing --output file\n\n{}", USAGE))?;
+    if !path.exists() {
+        bail!("Project path does not exist: {}", path.display());
+    }
+
+    match revision {
+        Some(revision) => {
+            let (structure, commit) = git_history::analyze_revision(&path, revision, &analysis)?;
+            snapshot::write(&snapshot::create(structure, Some(commit.id)), &output)?;
+        }
+        None => {
+            let structure = language::analyze_project(&path, &analysis)?;
+            snapshot::save(&structure, &output)?;
+        }
+    }
+    eprintln!("Snapshot written to {}", output.display());
+    Ok(())
+}
+
+fn hotspots_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut limit = 20;
+    let mut analysis = AnalysisOptions {
+        churn_days: Some(DEFAULT_CHURN_DAYS),
+        ..AnalysisOptions::default()
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--days" => {
+                let value = args.next().context("--days requires a value")?;
+                analysis.churn_days = Some(value.parse().with_context(|| format!("Invalid days: {}", value))?);
+            }
+            "--limit" | "-n" => {
+                let value = args.next().context("--limit requires a value")?;
+                limit = value.parse().with_context(|| format!("Invalid limit: {}", value))?;
+            }
+            "--include-ignored" => analysis.include_ignored = true,
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| PathBuf::from("."));
+    let structure = language::analyze_project(&path, &analysis)?;
+    if structure.modules.iter().all(|m| m.churn.is_none()) {
+        bail!("No git history for {}", path.display());
+    }
+
+    let metrics = ProjectAnalyzer::new(structure.clone()).calculate_metrics();
+    let mut ranked: Vec<_> = structure
+        .modules
+        .iter()
+        .filter_map(|module| Some((module, metrics.get(&module.id)?)))
+        .filter(|(_, metric)| metric.hotspot_score > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.hotspot_score.total_cmp(&a.1.hotspot_score));
+
+    println!("Changes in the last {} days:", analysis.churn_days.unwrap_or_default());
+    for (module, metric) in ranked.into_iter().take(limit) {
+        let churn = metric.churn.clone().unwrap_or_default();
+        println!(
+            "{:.2}  {}  {} commits, +{} -{} lines, {} authors; {} lines, complexity {}",
+            metric.hotspot_score,
+            module.name,
+            churn.commits,
+            churn.lines_added,
+            churn.lines_removed,
+            churn.authors,
+            metric.lines_of_code,
+            metric.complexity_score
+        );
+    }
+    Ok(())
+}
+
+fn owners_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut json = false;
+    let mut analysis = AnalysisOptions::default();
+
+    for arg in args {
+        match arg.as_str() {
+            "--blame" => analysis.blame = true,
+            "--json" => json = true,
+            "--include-ignored" => analysis.include_ignored = true,
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| PathBuf::from("."));
+    let structure = language::analyze_project(&path, &analysis)?;
+    let report = ownership::report(&structure);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    match &report.codeowners {
+        Some(file) => println!("Owners from {}\n", file),
+        None => println!("No CODEOWNERS file found\n"),
+    }
+    for team in &report.teams {
+        println!("{} ({} modules)", team.owner, team.modules.len());
+        for module in &team.modules {
+            println!("  {}", module);
+        }
+    }
+
+    let names: HashMap<&str, &str> = structure.modules.iter().map(|m| (m.id.as_str(), m.name.as_str())).collect();
+    if !report.boundary_edges.is_empty() {
+        println!("\nDependencies across team boundaries:");
+        for edge in &report.boundary_edges {
+            println!(
+                "  {} ({}) → {} ({})",
+                names[edge.from.as_str()],
+                edge.from_owners.join(" "),
+                names[edge.to.as_str()],
+                edge.to_owners.join(" ")
+            );
+        }
+    }
+    if !report.unowned_modules.is_empty() {
+        println!("\nModules without an owner:");
+        for module in &report.unowned_modules {
+            println!("  {}", module);
+        }
+    }
+
+    if analysis.blame {
+        println!("\nPrimary authors:");
+        for module in &structure.modules {
+            let authors: Vec<String> = module
+                .primary_authors
+                .iter()
+                .map(|a| format!("{} ({} lines)", a.name, a.lines))
+                .collect();
+            if !authors.is_empty() {
+                println!("  {}: {}", module.name, authors.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Prints recent commits, one per line, to pick revisions from
+fn history_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut revision = None;
+    let mut limit = 20;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--limit" | "-n" => {
+                let value = args.next().context("--limit requires a value")?;
+                limit = value.parse().with_context(|| format!("Invalid limit: {}", value))?;
+            }
+            "--rev" => revision = Some(args.next().context("--rev requires a value")?),
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| PathBuf::from("."));
+    for commit in git_history::list_commits(&path, revision.map(String::as_str), limit)? {
+        println!(
+            "{} {} {} ({})",
+            commit.short_id,
+            commit.time.format("%Y-%m-%d"),
+            commit.summary,
+            commit.author
+        );
+    }
+    Ok(())
+}
+
+// Prints the public API of the project's Rust libraries, one path per line
+fn api_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut analysis = AnalysisOptions::default();
+
+    for arg in args {
+        match arg.as_str() {
+            "--include-ignored" => analysis.include_ignored = true,
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let path = path.with_context(|| format!("Missing project path\n\n{}", USAGE))?;
+    if !path.exists() {
+        bail!("Project path does not exist: {}", path.display());
+    }
+
+    let structure = language::analyze_project(&path, &analysis)?;
+    for public in &structure.public_api {
+        println!("{} ({:?})", public.path, public.item_type);
+    }
+    Ok(())
+}
+
+fn trend_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut revision = None;
+    let mut output = None;
+    let mut json = false;
+    let mut limit = 20;
+    let mut step = 1;
+    let mut analysis = AnalysisOptions::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .with_context(|| format!("{} requires a value", name))
+        };
+        match arg.as_str() {
+            "--limit" | "-n" => {
+                let value = value("--limit")?;
+                limit = value.parse().with_context(|| format!("Invalid limit: {}", value))?;
+            }
+            "--step" => {
+                let value = value("--step")?;
+                step = value.parse().with_context(|| format!("Invalid step: {}", value))?;
+            }
+            "--rev" => revision = Some(value("--rev")?),
+            "--format" => {
+                json = match value("--format")?.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    other => bail!("Unsupported format: {}", other),
+                }
+            }
+            "--output" | "-o" => output = Some(PathBuf::from(value("--output")?)),
+            "--include-ignored" => analysis.include_ignored = true,
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| PathBuf::from("."));
+    let points = trend::collect(&path, revision.as_deref(), limit, step, &analysis)?;
+    let rendered = match json {
+        true => serde_json::to_string_pretty(&points)? + "\n",
+        false => trend::to_csv(&points),
+    };
+
+    match output {
+        Some(file) => fs::write(&file, rendered)
+            .with_context(|| format!("Failed to write {}", file.display()))?,
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+// `api-diff` compares public APIs, `diff` whole structures
+fn diff_command(args: &[String], public_api_only: bool) -> Result<()> {
+    let mut paths = Vec::new();
+    let mut json = false;
+    let mut analysis = AnalysisOptions::default();
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--include-ignored" => analysis.include_ignored = true,
+            flag if flag.starts_with('-') => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            _ if paths.len() < 2 => paths.push(arg),
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let [old, new] = paths.as_slice() else {
+        bail!("Expected an old and a new version\n\n{}", USAGE);
+    };
+
+    let old = snapshot::load_structure(old, &analysis)?;
+    let new = snapshot::load_structure(new, &analysis)?;
+    if public_api_only {
+        let diff = api_diff::diff(&old, &new);
+        if json {
+            println!("{}", serde_json::to_string_pretty(&diff)?);
+        } else {
+            print!("{}", api_diff::render_text(&diff));
+        }
+    } else {
+        let diff = structure_diff::diff(&old, &new);
+        if json {
+            println!("{}", serde_json::to_string_pretty(&diff)?);
+        } else {
+            print!("{}", diff.markdown);
+        }
+    }
+    Ok(())
+}
+
+fn parse_format(format: &str) -> Result<ExportFormat> {
+    match format {
+        "dot" | "gv" => Ok(ExportFormat::Dot),
+        "graphml" => Ok(ExportFormat::Graphml),
+        "gexf" => Ok(ExportFormat::Gexf),
+        "plantuml-component" | "puml" => Ok(ExportFormat::PlantumlComponent),
+        "plantuml-class" => Ok(ExportFormat::PlantumlClass),
+        "structurizr" | "dsl" => Ok(ExportFormat::Structurizr),
+        other => bail!("Unsupported format: {}", other),
+    }
+}
@@ -0,0 +1,137 @@
+use crate::analyzer::{self, ProjectAnalyzer, HIGH_COUPLING_DEPS, LARGE_MODULE_LINES};
+use crate::export::mermaid::{self, Diagram};
+use crate::models::*;
+use anyhow::{Context, Result};
+use minijinja::Environment;
+use serde::Serialize;
+use std::path::Path;
+
+// The layout "Generate Docs" has always produced. The comment at its top
+// documents the context available to templates.
+pub const DEFAULT_TEMPLATE: &str = include_str!("../templates/PROJECT_STRUCTURE.md.jinja");
+
+#[derive(Serialize)]
+pub struct DocContext<'a> {
+    pub project: ProjectInfo,
+    pub stats: Stats,
+    pub modules: Vec<ModuleContext<'a>>,
+    pub dependencies: &'a [Dependency],
+    pub relationships: &'a [Relationship],
+    pub problems: ProjectProblems,
+    pub diagrams: Vec<Diagram>,
+    pub structure: &'a ProjectStructure,
+}
+
+#[derive(Serialize)]
+pub struct ProjectInfo {
+    pub name: String,
+    pub root_path: String,
+}
+
+#[derive(Serialize)]
+pub struct Stats {
+    pub modules: usize,
+    pub dependencies: usize,
+    pub relationships: usize,
+    pub public_modules: usize,
+    pub tests: usize,
+}
+
+#[derive(Serialize)]
+pub struct ModuleContext<'a> {
+    #[serde(flatten)]
+    pub module: &'a Module,
+    pub depth: usize,
+    pub metrics: Option<ModuleMetrics>,
+    pub public_items: usize,
+    pub documented_items: usize,
+    pub doc_coverage: Option<usize>,
+    pub incoming: Vec<&'a Relationship>,
+    pub outgoing: Vec<&'a Relationship>,
+    pub problems: Vec<String>,
+}
+
+impl<'a> DocContext<'a> {
+    pub fn new(structure: &'a ProjectStructure) -> Self {
+        let mut metrics = ProjectAnalyzer::new(structure.clone()).calculate_metrics();
+        let problems = analyzer::find_problems(structure);
+
+        let modules = structure
+            .modules
+            .iter()
+            .map(|module| {
+                let (documented_items, public_items) = analyzer::doc_coverage(module);
+                let metrics = metrics.remove(&module.id);
+                ModuleContext {
+                    module,
+                    depth: module.name_segments().len().saturating_sub(1),
+                    public_items,
+                    documented_items,
+                    doc_coverage: (documented_items * 100).checked_div(public_items),
+                    incoming: structure.relationships.iter().filter(|r| r.to == module.id).collect(),
+                    outgoing: structure.relationships.iter().filter(|r| r.from == module.id).collect(),
+                    problems: module_problems(module, metrics.as_ref(), &problems),
+                    metrics,
+                }
+            })
+            .collect();
+
+        Self {
+            project: ProjectInfo {
+                name: Path::new(&structure.root_path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| structure.root_path.clone()),
+                root_path: structure.root_path.clone(),
+            },
+            stats: Stats {
+                modules: structure.modules.len(),
+                dependencies: structure.dependencies.len(),
+                relationships: structure.relationships.len(),
+                public_modules: structure.modules.iter().filter(|m| m.visibility == Visibility::Public).count(),
+                tests: structure.modules.iter().filter(|m| m.module_type == ModuleType::Test).count(),
+            },
+            modules,
+            dependencies: &structure.dependencies,
+            relationships: &structure.relationships,
+            problems,
+            diagrams: mermaid::render(structure),
+            structure,
+        }
+    }
+}
+
+fn module_problems(module: &Module, metrics: Option<&ModuleMetrics>, problems: &ProjectProblems) -> Vec<String> {
+    let mut found = Vec::new();
+    for cycle in problems.cycles.iter().filter(|c| c.contains(&module.id)) {
+        found.push(format!("Part of a dependency cycle: {}", cycle.join(" -> ")));
+    }
+    if problems.unused_modules.contains(&module.name) {
+        found.push("Not used by any other module".to_string());
+    }
+    if let Some(metrics) = metrics {
+        if metrics.lines_of_code > LARGE_MODULE_LINES {
+            found.push(format!("Large module ({} lines)", metrics.lines_of_code));
+        }
+        if metrics.incoming_deps > HIGH_COUPLING_DEPS {
+            found.push(format!("Highly coupled ({} incoming dependencies)", metrics.incoming_deps));
+        }
+    }
+    found
+}
+
+// Renders `template` (minijinja syntax) against the project's DocContext
+pub fn render(structure: &ProjectStructure, template: &str) -> Result<String> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_template("docs", template)
+        .context("Invalid documentation template")?;
+
+    let context = DocContext::new(structure);
+    env.get_template("docs")
+        .context("Invalid documentation template")?
+        .render(&context)
+        .context("Failed to render documentation template")
+}
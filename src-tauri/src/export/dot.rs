@@ -0,0 +1,101 @@
+use super::{internal_relationships, module_tree, Cluster};
+use crate::models::*;
+
+pub fn render(structure: &ProjectStructure) -> String {
+    let mut dot = String::new();
+    dot.push_str("digraph project {\n");
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str("    compound=true;\n");
+    dot.push_str("    node [fontname=\"Helvetica\", fontsize=10, style=\"rounded,filled\"];\n");
+    dot.push_str("    edge [fontname=\"Helvetica\", fontsize=9];\n\n");
+
+    let root = module_tree(structure);
+    let mut counter = 0;
+    write_cluster(&mut dot, &root, 1, &mut counter);
+
+    dot.push('\n');
+    for rel in internal_relationships(structure) {
+        let mut attrs = edge_style(&rel.rel_type);
+        if let Some(label) = &rel.label {
+            attrs.push(format!("label=\"{}\"", escape(label)));
+        }
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\" [{}];\n",
+            escape(&rel.from),
+            escape(&rel.to),
+            attrs.join(", ")
+        ));
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn write_cluster(dot: &mut String, cluster: &Cluster, level: usize, counter: &mut usize) {
+    let indent = "    ".repeat(level);
+    for module in &cluster.modules {
+        dot.push_str(&format!("{}{}\n", indent, node(module)));
+    }
+
+    for (name, child) in &cluster.children {
+        *counter += 1;
+        dot.push_str(&format!("{}subgraph cluster_{} {{\n", indent, counter));
+        dot.push_str(&format!("{}    label=\"{}\";\n", indent, escape(name)));
+        dot.push_str(&format!("{}    style=\"rounded\";\n", indent));
+        dot.push_str(&format!("{}    color=\"#888888\";\n", indent));
+        write_cluster(dot, child, level + 1, counter);
+        dot.push_str(&format!("{}}}\n", indent));
+    }
+}
+
+fn node(module: &Module) -> String {
+    let (shape, fill) = match module.module_type {
+        ModuleType::Binary => ("doubleoctagon", "#ffcdd2"),
+        ModuleType::Library => ("folder", "#bbdefb"),
+        ModuleType::Module => ("box", "#e0e0e0"),
+        ModuleType::Test => ("note", "#c8e6c9"),
+        ModuleType::Example => ("component", "#fff9c4"),
+        ModuleType::Benchmark => ("cds", "#ffe0b2"),
+    };
+    let border = match module.visibility {
+        Visibility::Public => "solid",
+        Visibility::Crate | Visibility::Super => "dashed",
+        Visibility::Private => "dotted",
+    };
+    let label = module
+        .name_segments()
+        .last()
+        .copied()
+        .unwrap_or(&module.name)
+        .to_string();
+
+    format!(
+        "\"{}\" [label=\"{}\", tooltip=\"{}\", shape={}, fillcolor=\"{}\", style=\"rounded,filled,{}\"];",
+        escape(&module.id),
+        escape(&label),
+        escape(&module.name),
+        shape,
+        fill,
+        border
+    )
+}
+
+fn edge_style(rel_type: &RelationType) -> Vec<String> {
+    let (color, style, arrowhead) = match rel_type {
+        RelationType::Uses => ("#2196f3", "solid", "normal"),
+        RelationType::Declares => ("#4caf50", "solid", "odiamond"),
+        RelationType::Inherits => ("#e91e63", "solid", "empty"),
+        RelationType::Binds => ("#ffc107", "dashed", "normal"),
+        RelationType::Invokes => ("#9c27b0", "dashed", "vee"),
+        RelationType::ReExports => ("#00897b", "dashed", "onormal"),
+    };
+    vec![
+        format!("color=\"{}\"", color),
+        format!("style={}", style),
+        format!("arrowhead={}", arrowhead),
+    ]
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,94 @@
+use super::graph::{self, xml_escape, AttrDef, AttrType, EDGE_ATTRIBUTES, NODE_ATTRIBUTES};
+use crate::models::*;
+
+pub fn render(structure: &ProjectStructure) -> String {
+    let graph = graph::build(structure);
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<gexf xmlns=\"http://gexf.net/1.3\"\n");
+    xml.push_str("      xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\n");
+    xml.push_str("      xsi:schemaLocation=\"http://gexf.net/1.3 http://gexf.net/1.3/gexf.xsd\"\n");
+    xml.push_str("      version=\"1.3\">\n");
+    xml.push_str("  <meta>\n");
+    xml.push_str("    <creator>Rust Project Visualizer</creator>\n");
+    xml.push_str(&format!(
+        "    <description>{}</description>\n",
+        xml_escape(&structure.root_path)
+    ));
+    xml.push_str("  </meta>\n");
+    xml.push_str("  <graph defaultedgetype=\"directed\" mode=\"static\">\n");
+
+    write_attributes(&mut xml, "node", NODE_ATTRIBUTES);
+    write_attributes(&mut xml, "edge", EDGE_ATTRIBUTES);
+
+    xml.push_str("    <nodes>\n");
+    for node in &graph.nodes {
+        xml.push_str(&format!(
+            "      <node id=\"{}\" label=\"{}\">\n",
+            xml_escape(&node.id),
+            xml_escape(&node.label)
+        ));
+        write_values(&mut xml, NODE_ATTRIBUTES, &node.attributes);
+        xml.push_str("      </node>\n");
+    }
+    xml.push_str("    </nodes>\n");
+
+    xml.push_str("    <edges>\n");
+    for edge in &graph.edges {
+        let label = edge
+            .label
+            .as_ref()
+            .map(|l| format!(" label=\"{}\"", xml_escape(l)))
+            .unwrap_or_default();
+        xml.push_str(&format!(
+            "      <edge id=\"{}\" source=\"{}\" target=\"{}\"{}>\n",
+            xml_escape(&edge.id),
+            xml_escape(&edge.source),
+            xml_escape(&edge.target),
+            label
+        ));
+        write_values(&mut xml, EDGE_ATTRIBUTES, &edge.attributes);
+        xml.push_str("      </edge>\n");
+    }
+    xml.push_str("    </edges>\n");
+
+    xml.push_str("  </graph>\n");
+    xml.push_str("</gexf>\n");
+    xml
+}
+
+// GEXF refers to attributes by index-like ids declared up front
+fn write_attributes(xml: &mut String, class: &str, attributes: &[AttrDef]) {
+    xml.push_str(&format!("    <attributes class=\"{}\">\n", class));
+    for (index, attr) in attributes.iter().enumerate() {
+        let attr_type = match attr.attr_type {
+            AttrType::String => "string",
+            AttrType::Int => "integer",
+            AttrType::Bool => "boolean",
+        };
+        xml.push_str(&format!(
+            "      <attribute id=\"{}\" title=\"{}\" type=\"{}\"/>\n",
+            index, attr.name, attr_type
+        ));
+    }
+    xml.push_str("    </attributes>\n");
+}
+
+fn write_values(xml: &mut String, definitions: &[AttrDef], values: &[(&'static str, graph::AttrValue)]) {
+    if values.is_empty() {
+        return;
+    }
+    xml.push_str("        <attvalues>\n");
+    for (name, value) in values {
+        let Some(index) = definitions.iter().position(|d| d.name == *name) else {
+            continue;
+        };
+        xml.push_str(&format!(
+            "          <attvalue for=\"{}\" value=\"{}\"/>\n",
+            index,
+            xml_escape(&value.render())
+        ));
+    }
+    xml.push_str("        </attvalues>\n");
+}
@@ -0,0 +1,224 @@
+use crate::analyzer::{self, ProjectAnalyzer, HIGH_COUPLING_DEPS, LARGE_MODULE_LINES};
+use crate::models::*;
+use serde::Serialize;
+use std::collections::HashSet;
+
+// A flat, attribute-based view of the project shared by the GraphML and
+// GEXF writers: modules, items and dependencies are nodes, relationships
+// (plus module -> item containment) are edges
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum AttrType {
+    String,
+    Int,
+    Bool,
+}
+
+pub struct AttrDef {
+    pub name: &'static str,
+    pub attr_type: AttrType,
+}
+
+pub enum AttrValue {
+    String(String),
+    Int(usize),
+    Bool(bool),
+}
+
+impl AttrValue {
+    pub fn render(&self) -> String {
+        match self {
+            AttrValue::String(s) => s.clone(),
+            AttrValue::Int(i) => i.to_string(),
+            AttrValue::Bool(b) => b.to_string(),
+        }
+    }
+}
+
+pub struct GraphNode {
+    pub id: String,
+    pub label: String,
+    pub attributes: Vec<(&'static str, AttrValue)>,
+}
+
+pub struct GraphEdge {
+    pub id: String,
+    pub source: String,
+    pub target: String,
+    pub label: Option<String>,
+    pub attributes: Vec<(&'static str, AttrValue)>,
+}
+
+pub const NODE_ATTRIBUTES: &[AttrDef] = &[
+    AttrDef { name: "kind", attr_type: AttrType::String },
+    AttrDef { name: "name", attr_type: AttrType::String },
+    AttrDef { name: "path", attr_type: AttrType::String },
+    AttrDef { name: "module_type", attr_type: AttrType::String },
+    AttrDef { name: "visibility", attr_type: AttrType::String },
+    AttrDef { name: "language", attr_type: AttrType::String },
+    AttrDef { name: "item_type", attr_type: AttrType::String },
+    AttrDef { name: "version", attr_type: AttrType::String },
+    AttrDef { name: "dep_type", attr_type: AttrType::String },
+    AttrDef { name: "lines_of_code", attr_type: AttrType::Int },
+    AttrDef { name: "incoming_deps", attr_type: AttrType::Int },
+    AttrDef { name: "outgoing_deps", attr_type: AttrType::Int },
+    AttrDef { name: "complexity_score", attr_type: AttrType::Int },
+    AttrDef { name: "in_cycle", attr_type: AttrType::Bool },
+    AttrDef { name: "unused", attr_type: AttrType::Bool },
+    AttrDef { name: "large", attr_type: AttrType::Bool },
+    AttrDef { name: "highly_coupled", attr_type: AttrType::Bool },
+];
+
+pub const EDGE_ATTRIBUTES: &[AttrDef] = &[
+    AttrDef { name: "rel_type", attr_type: AttrType::String },
+    AttrDef { name: "label", attr_type: AttrType::String },
+];
+
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+pub fn build(structure: &ProjectStructure) -> Graph {
+    let analyzer = ProjectAnalyzer::new(structure.clone());
+    let metrics = analyzer.calculate_metrics();
+    let problems = analyzer::find_problems(structure);
+
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+
+    for module in &structure.modules {
+        let in_cycle = problems.cycles.iter().any(|c| c.contains(&module.id));
+        let unused = problems.unused_modules.contains(&module.name);
+
+        let mut attributes = vec![
+            ("kind", AttrValue::String("module".to_string())),
+            ("name", AttrValue::String(module.name.clone())),
+            ("path", AttrValue::String(module.path.clone())),
+            ("module_type", AttrValue::String(serde_name(&module.module_type))),
+            ("visibility", AttrValue::String(serde_name(&module.visibility))),
+            ("language", AttrValue::String(serde_name(&module.language))),
+        ];
+        if let Some(metric) = metrics.get(&module.id) {
+            attributes.extend([
+                ("lines_of_code", AttrValue::Int(metric.lines_of_code)),
+                ("incoming_deps", AttrValue::Int(metric.incoming_deps)),
+                ("outgoing_deps", AttrValue::Int(metric.outgoing_deps)),
+                ("complexity_score", AttrValue::Int(metric.complexity_score)),
+                ("large", AttrValue::Bool(metric.lines_of_code > LARGE_MODULE_LINES)),
+                ("highly_coupled", AttrValue::Bool(metric.incoming_deps > HIGH_COUPLING_DEPS)),
+            ]);
+        }
+        attributes.extend([("in_cycle", AttrValue::Bool(in_cycle)), ("unused", AttrValue::Bool(unused))]);
+
+        nodes.push(GraphNode {
+            id: module.id.clone(),
+            label: module.name.clone(),
+            attributes,
+        });
+
+        for (index, item) in module.items.iter().enumerate() {
+            let item_id = format!("item:{}:{}", module.id, index);
+            nodes.push(GraphNode {
+                id: item_id.clone(),
+                label: item.name.clone(),
+                attributes: vec![
+                    ("kind", AttrValue::String("item".to_string())),
+                    ("name", AttrValue::String(item.name.clone())),
+                    ("item_type", AttrValue::String(serde_name(&item.item_type))),
+                    ("visibility", AttrValue::String(serde_name(&item.visibility))),
+                ],
+            });
+            edges.push(GraphEdge {
+                id: format!("e{}", edges.len()),
+                source: module.id.clone(),
+                target: item_id,
+                label: None,
+                attributes: vec![("rel_type", AttrValue::String("contains".to_string()))],
+            });
+        }
+    }
+
+    let mut dependency_names = HashSet::new();
+    for dep in &structure.dependencies {
+        if !dependency_names.insert(dep.name.clone()) {
+            continue;
+        }
+        nodes.push(GraphNode {
+            id: dependency_id(&dep.name),
+            label: dep.name.clone(),
+            attributes: vec![
+                ("kind", AttrValue::String("dependency".to_string())),
+                ("name", AttrValue::String(dep.name.clone())),
+                ("version", AttrValue::String(dep.version.clone())),
+                ("dep_type", AttrValue::String(serde_name(&dep.dep_type))),
+            ],
+        });
+    }
+
+    // Edges to names that are neither modules nor dependencies (std, system
+    // headers...) have no node to attach to
+    let module_ids: HashSet<&str> = structure.modules.iter().map(|m| m.id.as_str()).collect();
+    let resolve = |target: &str| -> Option<String> {
+        if module_ids.contains(target) {
+            return Some(target.to_string());
+        }
+        dependency_names
+            .iter()
+            .find(|name| crate_name(name) == target)
+            .map(|name| dependency_id(name))
+    };
+
+    for rel in &structure.relationships {
+        let (Some(source), Some(target)) = (resolve(&rel.from), resolve(&rel.to)) else {
+            continue;
+        };
+        let mut attributes = vec![("rel_type", AttrValue::String(serde_name(&rel.rel_type)))];
+        if let Some(label) = &rel.label {
+            attributes.push(("label", AttrValue::String(label.clone())));
+        }
+        edges.push(GraphEdge {
+            id: format!("e{}", edges.len()),
+            source,
+            target,
+            label: rel.label.clone(),
+            attributes,
+        });
+    }
+
+    Graph { nodes, edges }
+}
+
+fn dependency_id(name: &str) -> String {
+    format!("dep:{}", name)
+}
+
+// How analyzers refer to a dependency in `Uses` edges, e.g. `serde-json` -> `serde_json`
+fn crate_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .trim_start_matches('_')
+        .to_string()
+}
+
+// The serialized (lowercase / snake_case) name of an enum value
+fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+pub fn xml_escape(value: &str) -> String {
+    // Control characters are not allowed in XML 1.0 at all
+    value
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,67 @@
+use super::graph::{self, xml_escape, AttrDef, AttrType, EDGE_ATTRIBUTES, NODE_ATTRIBUTES};
+use crate::models::*;
+
+pub fn render(structure: &ProjectStructure) -> String {
+    let graph = graph::build(structure);
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\"\n");
+    xml.push_str("         xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\n");
+    xml.push_str("         xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n");
+
+    write_keys(&mut xml, "node", "n", NODE_ATTRIBUTES);
+    write_keys(&mut xml, "edge", "e", EDGE_ATTRIBUTES);
+
+    xml.push_str(&format!(
+        "  <graph id=\"{}\" edgedefault=\"directed\">\n",
+        xml_escape(&structure.root_path)
+    ));
+
+    for node in &graph.nodes {
+        xml.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&node.id)));
+        for (name, value) in &node.attributes {
+            xml.push_str(&format!(
+                "      <data key=\"n_{}\">{}</data>\n",
+                name,
+                xml_escape(&value.render())
+            ));
+        }
+        xml.push_str("    </node>\n");
+    }
+
+    for edge in &graph.edges {
+        xml.push_str(&format!(
+            "    <edge id=\"{}\" source=\"{}\" target=\"{}\">\n",
+            xml_escape(&edge.id),
+            xml_escape(&edge.source),
+            xml_escape(&edge.target)
+        ));
+        for (name, value) in &edge.attributes {
+            xml.push_str(&format!(
+                "      <data key=\"e_{}\">{}</data>\n",
+                name,
+                xml_escape(&value.render())
+            ));
+        }
+        xml.push_str("    </edge>\n");
+    }
+
+    xml.push_str("  </graph>\n");
+    xml.push_str("</graphml>\n");
+    xml
+}
+
+fn write_keys(xml: &mut String, domain: &str, prefix: &str, attributes: &[AttrDef]) {
+    for attr in attributes {
+        let attr_type = match attr.attr_type {
+            AttrType::String => "string",
+            AttrType::Int => "int",
+            AttrType::Bool => "boolean",
+        };
+        xml.push_str(&format!(
+            "  <key id=\"{}_{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+            prefix, attr.name, domain, attr.name, attr_type
+        ));
+    }
+}
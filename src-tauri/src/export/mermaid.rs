@@ -0,0 +1,179 @@
+use super::{internal_relationships, safe_id};
+use crate::models::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+// Larger graphs are split into one diagram per subtree plus an overview
+pub const MAX_DIAGRAM_MODULES: usize = 40;
+
+#[derive(Serialize)]
+pub struct Diagram {
+    pub title: Option<String>,
+    pub source: String,
+}
+
+pub fn render(structure: &ProjectStructure) -> Vec<Diagram> {
+    let modules: Vec<&Module> = structure.modules.iter().collect();
+    if modules.len() <= MAX_DIAGRAM_MODULES {
+        return vec![Diagram {
+            title: None,
+            source: render_modules(structure, &modules),
+        }];
+    }
+
+    let groups = split(modules, 0);
+    let mut diagrams = vec![Diagram {
+        title: Some("Overview".to_string()),
+        source: render_overview(structure, &groups),
+    }];
+    for (title, modules) in &groups {
+        diagrams.push(Diagram {
+            title: Some(title.clone()),
+            source: render_modules(structure, modules),
+        });
+    }
+    diagrams
+}
+
+// Groups modules by their first `level + 1` name segments, splitting further
+// while a group is still too large. Single-module groups are collected into
+// one group for their parent so small leaf modules don't each get a diagram.
+fn split(modules: Vec<&Module>, level: usize) -> Vec<(String, Vec<&Module>)> {
+    let mut groups: BTreeMap<String, Vec<&Module>> = BTreeMap::new();
+    for module in modules {
+        let segments = module.name_segments();
+        let key = segments[..segments.len().min(level + 1)].join(module.name_separator());
+        groups.entry(key).or_default().push(module);
+    }
+
+    let mut result = Vec::new();
+    let mut rest = Vec::new();
+    let mut parent = None;
+    for (key, modules) in groups {
+        if modules.len() == 1 {
+            parent = Some(parent_name(modules[0], level));
+            rest.extend(modules);
+        } else if modules.len() > MAX_DIAGRAM_MODULES && modules.iter().any(|m| m.name_segments().len() > level + 1) {
+            result.extend(split(modules, level + 1));
+        } else {
+            result.push((key, modules));
+        }
+    }
+    if !rest.is_empty() {
+        let title = match parent.flatten() {
+            Some(parent) if result.is_empty() => parent,
+            Some(parent) => format!("{} (other modules)", parent),
+            None => "Top-level modules".to_string(),
+        };
+        result.push((title, rest));
+    }
+    result
+}
+
+fn parent_name(module: &Module, level: usize) -> Option<String> {
+    let segments = module.name_segments();
+    (level > 0 && segments.len() > level).then(|| segments[..level].join(module.name_separator()))
+}
+
+fn render_modules(structure: &ProjectStructure, modules: &[&Module]) -> String {
+    let members: HashSet<&str> = modules.iter().map(|m| m.id.as_str()).collect();
+    let names: BTreeMap<&str, &str> = structure
+        .modules
+        .iter()
+        .map(|m| (m.id.as_str(), m.name.as_str()))
+        .collect();
+
+    let mut mermaid = String::from("graph TD\n");
+    mermaid.push_str("    classDef external fill:#f5f5f5,stroke:#999,stroke-dasharray:3 3\n");
+    for module in modules {
+        mermaid.push_str(&format!("    {}[\"{}\"]\n", node_id(&module.id), escape(&module.name)));
+    }
+
+    // Neighbours from other subtrees are drawn as external nodes
+    let mut externals = BTreeMap::new();
+    let mut seen = HashSet::new();
+    let mut edges = String::new();
+    for rel in internal_relationships(structure) {
+        let (from_member, to_member) = (members.contains(rel.from.as_str()), members.contains(rel.to.as_str()));
+        if !from_member && !to_member {
+            continue;
+        }
+        if !seen.insert((rel.from.as_str(), rel.to.as_str(), relation_arrow(&rel.rel_type))) {
+            continue;
+        }
+        for (id, member) in [(rel.from.as_str(), from_member), (rel.to.as_str(), to_member)] {
+            if !member {
+                externals.insert(id, names[id]);
+            }
+        }
+        edges.push_str(&format!(
+            "    {} {} {}\n",
+            node_id(&rel.from),
+            relation_arrow(&rel.rel_type),
+            node_id(&rel.to)
+        ));
+    }
+    for (id, name) in externals {
+        mermaid.push_str(&format!("    {}[\"{}\"]:::external\n", node_id(id), escape(name)));
+    }
+    mermaid.push_str(&edges);
+    mermaid
+}
+
+fn render_overview(structure: &ProjectStructure, groups: &[(String, Vec<&Module>)]) -> String {
+    let mut group_of = BTreeMap::new();
+    let mut mermaid = String::from("graph TD\n");
+    for (index, (title, modules)) in groups.iter().enumerate() {
+        let id = format!("group{}", index);
+        mermaid.push_str(&format!(
+            "    {}[\"{} ({} modules)\"]\n",
+            id,
+            escape(title),
+            modules.len()
+        ));
+        for module in modules {
+            group_of.insert(module.id.as_str(), id.clone());
+        }
+    }
+
+    let mut counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for rel in internal_relationships(structure) {
+        if rel.rel_type == RelationType::Declares {
+            continue;
+        }
+        let (from, to) = (&group_of[rel.from.as_str()], &group_of[rel.to.as_str()]);
+        if from != to {
+            *counts.entry((from, to)).or_default() += 1;
+        }
+    }
+    for ((from, to), count) in counts {
+        mermaid.push_str(&format!("    {} -->|{}| {}\n", from, count, to));
+    }
+    mermaid
+}
+
+fn relation_arrow(rel_type: &RelationType) -> &'static str {
+    match rel_type {
+        RelationType::Uses => "-->",
+        RelationType::Declares => "-.->",
+        RelationType::Inherits => "==>|inherits|",
+        RelationType::Binds => "-.->|binds|",
+        RelationType::Invokes => "-.->|invokes|",
+        RelationType::ReExports => "-.->|re-exports|",
+    }
+}
+
+// Module ids escaped with safe_id (`a_b` -> `ma_5f_b`, `ab` -> `mab`). The
+// prefix keeps ids like `end` from clashing with Mermaid keywords.
+pub fn node_id(id: &str) -> String {
+    format!("m{}", safe_id(id))
+}
+
+// Labels are quoted; quotes and markup characters become Mermaid entity codes
+fn escape(label: &str) -> String {
+    label
+        .replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
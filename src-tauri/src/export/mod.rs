@@ -0,0 +1,139 @@
+pub mod dot;
+pub mod gexf;
+pub mod graph;
+pub mod graphml;
+pub mod mermaid;
+pub mod plantuml;
+pub mod structurizr;
+
+use crate::models::*;
+use std::collections::{BTreeMap, HashSet};
+
+// Renders the structure in the requested format, after applying the
+// subtree/depth filter
+pub fn export(structure: &ProjectStructure, options: &ExportOptions) -> String {
+    let filtered = filter_structure(structure, options);
+    match options.format {
+        ExportFormat::Dot => dot::render(&filtered),
+        ExportFormat::Graphml => graphml::render(&filtered),
+        ExportFormat::Gexf => gexf::render(&filtered),
+        ExportFormat::PlantumlComponent => plantuml::render_components(&filtered),
+        ExportFormat::PlantumlClass => plantuml::render_classes(&filtered),
+        ExportFormat::Structurizr => structurizr::render(&filtered),
+    }
+}
+
+// Keeps the modules below `options.root` down to `options.depth` levels and
+// the relationships between them
+pub fn filter_structure(structure: &ProjectStructure, options: &ExportOptions) -> ProjectStructure {
+    let modules: Vec<Module> = structure
+        .modules
+        .iter()
+        .filter(|module| {
+            let segments = module.name_segments();
+            let root_len = match &options.root {
+                Some(root) => {
+                    let root: Vec<&str> = root.split(module.name_separator()).collect();
+                    if !segments.starts_with(&root) {
+                        return false;
+                    }
+                    root.len()
+                }
+                None => 0,
+            };
+            options.depth.is_none_or(|depth| segments.len() - root_len <= depth)
+        })
+        .cloned()
+        .collect();
+
+    // Drop edges touching filtered-out modules; edges to external names
+    // (dependencies, std) stay for the formats that can show them
+    let kept: HashSet<&str> = modules.iter().map(|m| m.id.as_str()).collect();
+    let removed: HashSet<&str> = structure
+        .modules
+        .iter()
+        .map(|m| m.id.as_str())
+        .filter(|id| !kept.contains(id))
+        .collect();
+    let relationships = structure
+        .relationships
+        .iter()
+        .filter(|r| !removed.contains(r.from.as_str()) && !removed.contains(r.to.as_str()))
+        .cloned()
+        .collect();
+
+    ProjectStructure {
+        root_path: structure.root_path.clone(),
+        modules,
+        dependencies: structure.dependencies.clone(),
+        relationships,
+        diagnostics: structure.diagnostics.clone(),
+        public_api: structure.public_api.clone(),
+    }
+}
+
+// Relationships whose both ends are modules of the structure
+pub fn internal_relationships(structure: &ProjectStructure) -> impl Iterator<Item = &Relationship> {
+    let ids: HashSet<&str> = structure.modules.iter().map(|m| m.id.as_str()).collect();
+    structure
+        .relationships
+        .iter()
+        .filter(move |r| ids.contains(r.from.as_str()) && ids.contains(r.to.as_str()))
+}
+
+// Modules grouped by parent path; `children` are nested clusters keyed by
+// their full path
+#[derive(Default)]
+pub struct Cluster<'a> {
+    pub modules: Vec<&'a Module>,
+    pub children: BTreeMap<String, Cluster<'a>>,
+}
+
+pub fn module_tree(structure: &ProjectStructure) -> Cluster<'_> {
+    let mut root = Cluster::default();
+    for module in &structure.modules {
+        let segments = module.name_segments();
+        let mut cluster = &mut root;
+        let mut path = Vec::new();
+        for segment in &segments[..segments.len() - 1] {
+            path.push(*segment);
+            cluster = cluster
+                .children
+                .entry(path.join(module.name_separator()))
+                .or_default();
+        }
+        cluster.modules.push(module);
+    }
+    adopt_parents(&mut root);
+    root
+}
+
+// A module with submodules is drawn inside the cluster of its children
+fn adopt_parents(cluster: &mut Cluster) {
+    let modules = std::mem::take(&mut cluster.modules);
+    for module in modules {
+        match cluster.children.get_mut(&module.name) {
+            Some(child) => child.modules.insert(0, module),
+            None => cluster.modules.push(module),
+        }
+    }
+    for child in cluster.children.values_mut() {
+        adopt_parents(child);
+    }
+}
+
+// Identifier safe for PlantUML aliases, Structurizr DSL and Mermaid: ASCII
+// alphanumerics are kept and every other character becomes `_<hex>_`, so
+// distinct values never collide (`my-comp` -> `my_2d_comp`, `my_comp` ->
+// `my_5f_comp`)
+pub fn safe_id(value: &str) -> String {
+    let mut id = String::new();
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c);
+        } else {
+            id.push_str(&format!("_{:x}_", c as u32));
+        }
+    }
+    id
+}
@@ -0,0 +1,195 @@
+use super::{internal_relationships, module_tree, safe_id, Cluster};
+use crate::models::*;
+use std::collections::{HashMap, HashSet};
+
+pub fn render_components(structure: &ProjectStructure) -> String {
+    let mut uml = String::new();
+    uml.push_str("@startuml\n");
+    uml.push_str("skinparam componentStyle rectangle\n");
+    uml.push_str("left to right direction\n\n");
+
+    write_packages(&mut uml, &module_tree(structure), 0);
+
+    uml.push('\n');
+    for rel in internal_relationships(structure) {
+        let arrow = match rel.rel_type {
+            RelationType::Uses => "-->",
+            RelationType::Declares => "..>",
+            RelationType::Inherits => "--|>",
+            RelationType::Binds | RelationType::Invokes | RelationType::ReExports => "..>",
+        };
+        let description = match &rel.label {
+            Some(label) => format!("{} {}", relation_name(&rel.rel_type), label),
+            None => relation_name(&rel.rel_type).to_string(),
+        };
+        uml.push_str(&format!(
+            "{} {} {} : {}\n",
+            safe_id(&rel.from),
+            arrow,
+            safe_id(&rel.to),
+            escape(&description)
+        ));
+    }
+
+    uml.push_str("@enduml\n");
+    uml
+}
+
+fn write_packages(uml: &mut String, cluster: &Cluster, level: usize) {
+    let indent = "  ".repeat(level);
+    for module in &cluster.modules {
+        let label = module.name_segments().last().copied().unwrap_or(&module.name).to_string();
+        let stereotype = match module.module_type {
+            ModuleType::Binary => " <<binary>>",
+            ModuleType::Library => " <<library>>",
+            ModuleType::Test => " <<test>>",
+            ModuleType::Example => " <<example>>",
+            ModuleType::Benchmark => " <<bench>>",
+            ModuleType::Module => "",
+        };
+        uml.push_str(&format!(
+            "{}[{}] as {}{}\n",
+            indent,
+            escape(&label),
+            safe_id(&module.id),
+            stereotype
+        ));
+    }
+    for (name, child) in &cluster.children {
+        uml.push_str(&format!("{}package \"{}\" {{\n", indent, escape(name)));
+        write_packages(uml, child, level + 1);
+        uml.push_str(&format!("{}}}\n", indent));
+    }
+}
+
+// Structs, traits/interfaces and enums per module, with the methods of their
+// impl blocks and the implemented traits / base classes as relations
+pub fn render_classes(structure: &ProjectStructure) -> String {
+    let is_type = |item: &Item| matches!(item.item_type, ItemType::Struct | ItemType::Trait | ItemType::Enum);
+
+    // type name -> aliases of every class with that name
+    let mut by_name: HashMap<&str, Vec<(String, &str)>> = HashMap::new();
+    let mut interfaces = HashSet::new();
+    for module in &structure.modules {
+        for item in module.items.iter().filter(|i| is_type(i)) {
+            if item.item_type == ItemType::Trait {
+                interfaces.insert(class_alias(module, &item.name));
+            }
+            by_name
+                .entry(item.name.as_str())
+                .or_default()
+                .push((class_alias(module, &item.name), module.id.as_str()));
+        }
+    }
+    // Prefer the class in the same module, otherwise the only one with that name
+    let resolve = |name: &str, module_id: &str| -> Option<String> {
+        let name = name.rsplit(['.', ':']).next().unwrap_or(name);
+        let candidates = by_name.get(name)?;
+        candidates
+            .iter()
+            .find(|(_, id)| *id == module_id)
+            .or_else(|| (candidates.len() == 1).then(|| &candidates[0]))
+            .map(|(alias, _)| alias.clone())
+    };
+
+    // class alias -> methods from every impl block of the type
+    let mut methods: HashMap<String, Vec<&Item>> = HashMap::new();
+    let mut relations = Vec::new();
+    for module in &structure.modules {
+        for block in &module.impls {
+            let Some(alias) = resolve(&block.self_type, &module.id) else {
+                continue;
+            };
+            methods.entry(alias.clone()).or_default().extend(block.methods.iter());
+            if let Some(trait_alias) = block.trait_name.as_ref().and_then(|t| resolve(t, &module.id)) {
+                relations.push(format!("{} ..|> {}", alias, trait_alias));
+            }
+        }
+        for item in module.items.iter().filter(|i| is_type(i)) {
+            let alias = class_alias(module, &item.name);
+            for base in &item.bases {
+                if let Some(base_alias) = resolve(base, &module.id) {
+                    let implements = interfaces.contains(&base_alias) && item.item_type != ItemType::Trait;
+                    let arrow = if implements { "..|>" } else { "--|>" };
+                    relations.push(format!("{} {} {}", alias, arrow, base_alias));
+                }
+            }
+        }
+    }
+
+    let mut uml = String::new();
+    uml.push_str("@startuml\n");
+    uml.push_str("hide empty members\n\n");
+
+    for module in &structure.modules {
+        let types: Vec<&Item> = module.items.iter().filter(|i| is_type(i)).collect();
+        if types.is_empty() {
+            continue;
+        }
+        uml.push_str(&format!("package \"{}\" {{\n", escape(&module.name)));
+        for item in types {
+            let alias = class_alias(module, &item.name);
+            let keyword = match item.item_type {
+                ItemType::Trait => "interface",
+                ItemType::Enum => "enum",
+                _ => "class",
+            };
+            let stereotype = match (module.language, &item.item_type) {
+                (Language::Rust, ItemType::Struct) => " <<struct>>",
+                (Language::Rust, ItemType::Trait) => " <<trait>>",
+                _ => "",
+            };
+            uml.push_str(&format!(
+                "  {} \"{}\" as {}{} {{\n",
+                keyword,
+                escape(&item.name),
+                alias,
+                stereotype
+            ));
+            for method in methods.get(&alias).into_iter().flatten() {
+                uml.push_str(&format!("    {}{}()\n", visibility_mark(&method.visibility), method.name));
+            }
+            uml.push_str("  }\n");
+        }
+        uml.push_str("}\n");
+    }
+
+    uml.push('\n');
+    relations.sort();
+    relations.dedup();
+    for relation in relations {
+        uml.push_str(&relation);
+        uml.push('\n');
+    }
+
+    uml.push_str("@enduml\n");
+    uml
+}
+
+// Neither part can contain NUL, so escaping the joined pair stays unique
+fn class_alias(module: &Module, name: &str) -> String {
+    safe_id(&format!("{}\0{}", module.id, name))
+}
+
+fn visibility_mark(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "+",
+        Visibility::Crate | Visibility::Super => "~",
+        Visibility::Private => "-",
+    }
+}
+
+pub fn relation_name(rel_type: &RelationType) -> &'static str {
+    match rel_type {
+        RelationType::Uses => "uses",
+        RelationType::Declares => "declares",
+        RelationType::Inherits => "inherits",
+        RelationType::Binds => "binds",
+        RelationType::Invokes => "invokes",
+        RelationType::ReExports => "re-exports",
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('"', "'")
+}
@@ -0,0 +1,173 @@
+use super::plantuml::relation_name;
+use super::{internal_relationships, safe_id};
+use crate::models::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+// One container per crate (or per non-Rust language), one component per
+// top-level module inside it
+struct Container {
+    name: String,
+    technology: String,
+    // component name -> module ids rolled up into it
+    components: BTreeMap<String, Vec<String>>,
+}
+
+pub fn render(structure: &ProjectStructure) -> String {
+    let project_name = Path::new(&structure.root_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| structure.root_path.clone());
+
+    let mut containers: BTreeMap<String, Container> = BTreeMap::new();
+    for module in &structure.modules {
+        let (key, name, technology) = container_of(module, &project_name);
+        containers.entry(key).or_insert_with(|| Container {
+            name,
+            technology,
+            components: BTreeMap::new(),
+        });
+    }
+
+    // Components are named relative to what all modules of a container share,
+    // e.g. the crate prefix in a workspace or the Go module path
+    let mut component_of: HashMap<&str, String> = HashMap::new();
+    for (key, container) in containers.iter_mut() {
+        let modules: Vec<&Module> = structure
+            .modules
+            .iter()
+            .filter(|m| container_of(m, &project_name).0 == *key)
+            .collect();
+        let prefix_len = common_prefix_len(&modules);
+        for module in modules {
+            let segments = module.name_segments();
+            let component = segments
+                .get(prefix_len)
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| segments[segments.len() - 1].to_string());
+            container
+                .components
+                .entry(component.clone())
+                .or_default()
+                .push(module.id.clone());
+            component_of.insert(module.id.as_str(), component_id(key, &component));
+        }
+    }
+
+    // Module relationships rolled up to component pairs
+    let mut relations: BTreeMap<(String, String), BTreeSet<&str>> = BTreeMap::new();
+    for rel in internal_relationships(structure) {
+        if rel.rel_type == RelationType::Declares {
+            continue;
+        }
+        let (Some(from), Some(to)) = (component_of.get(rel.from.as_str()), component_of.get(rel.to.as_str())) else {
+            continue;
+        };
+        if from != to {
+            relations
+                .entry((from.clone(), to.clone()))
+                .or_default()
+                .insert(relation_name(&rel.rel_type));
+        }
+    }
+
+    let mut dsl = String::new();
+    dsl.push_str(&format!(
+        "workspace \"{}\" \"Generated by Rust Project Visualizer\" {{\n\n",
+        escape(&project_name)
+    ));
+    dsl.push_str("    model {\n");
+    dsl.push_str(&format!("        system = softwareSystem \"{}\" {{\n", escape(&project_name)));
+    for (key, container) in &containers {
+        dsl.push_str(&format!(
+            "            {} = container \"{}\" \"\" \"{}\" {{\n",
+            container_id(key),
+            escape(&container.name),
+            escape(&container.technology)
+        ));
+        for (component, modules) in &container.components {
+            dsl.push_str(&format!(
+                "                {} = component \"{}\" \"{} module(s)\" \"{}\"\n",
+                component_id(key, component),
+                escape(component),
+                modules.len(),
+                escape(&container.technology)
+            ));
+        }
+        dsl.push_str("            }\n");
+    }
+    dsl.push_str("        }\n\n");
+
+    for ((from, to), kinds) in &relations {
+        let description = kinds.iter().copied().collect::<Vec<_>>().join(", ");
+        dsl.push_str(&format!("        {} -> {} \"{}\"\n", from, to, description));
+    }
+    dsl.push_str("    }\n\n");
+
+    dsl.push_str("    views {\n");
+    dsl.push_str("        container system \"Containers\" {\n");
+    dsl.push_str("            include *\n");
+    dsl.push_str("            autoLayout lr\n");
+    dsl.push_str("        }\n");
+    for key in containers.keys() {
+        dsl.push_str(&format!(
+            "        component {} \"{}_components\" {{\n",
+            container_id(key),
+            container_id(key)
+        ));
+        dsl.push_str("            include *\n");
+        dsl.push_str("            autoLayout lr\n");
+        dsl.push_str("        }\n");
+    }
+    dsl.push_str("    }\n");
+    dsl.push_str("}\n");
+    dsl
+}
+
+// (key, display name, technology)
+fn container_of(module: &Module, project_name: &str) -> (String, String, String) {
+    match (&module.language, &module.package) {
+        (Language::Rust, Some(package)) => (package.clone(), package.clone(), "Rust".to_string()),
+        (Language::Rust, None) => (project_name.to_string(), project_name.to_string(), "Rust".to_string()),
+        (Language::TypeScript | Language::JavaScript, _) => {
+            ("frontend".to_string(), "Frontend".to_string(), "TypeScript".to_string())
+        }
+        (Language::C | Language::Cpp, _) => ("native".to_string(), "Native code".to_string(), "C/C++".to_string()),
+        (language, _) => {
+            let name = format!("{:?}", language);
+            (name.to_lowercase(), name.clone(), name)
+        }
+    }
+}
+
+fn common_prefix_len(modules: &[&Module]) -> usize {
+    let Some(first) = modules.first() else {
+        return 0;
+    };
+    let first = first.name_segments();
+    let mut len = first.len().saturating_sub(1);
+    for module in &modules[1..] {
+        let segments = module.name_segments();
+        len = len.min(segments.len().saturating_sub(1));
+        len = first
+            .iter()
+            .zip(&segments)
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count();
+    }
+    len
+}
+
+fn container_id(key: &str) -> String {
+    format!("container_{}", safe_id(key))
+}
+
+// Neither part can contain NUL, so escaping the joined pair stays unique
+fn component_id(container: &str, component: &str) -> String {
+    format!("component_{}", safe_id(&format!("{}\0{}", container, component)))
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
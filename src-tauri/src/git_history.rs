@@ -0,0 +1,255 @@
+use crate::language::{self, ParseCache};
+use crate::models::*;
+use anyhow::{bail, Context, Result};
+use git2::{Commit, Delta, FileMode, ObjectType, Oid, Repository, Tree};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+// git's mode for symbolic links, which are not followed in checkouts
+const SYMLINK_MODE: i32 = 0o120000;
+
+// Most recent commits reachable from `revision` (HEAD by default), following
+// first parents so merged branches don't crowd out the mainline
+pub fn list_commits(path: &Path, revision: Option<&str>, limit: usize) -> Result<Vec<CommitInfo>> {
+    let repo = open(path)?;
+    let start = resolve(&repo, revision.unwrap_or("HEAD"))?;
+
+    let mut walk = repo.revwalk()?;
+    walk.push(start.id())?;
+    walk.simplify_first_parent()?;
+    walk.take(limit)
+        .map(|oid| Ok(commit_info(&repo.find_commit(oid?)?)))
+        .collect()
+}
+
+// Analyzes the project as it was at `revision`. The commit's files are
+// written to a temporary directory, so the working tree and index stay
+// untouched; module paths are reported relative to `path` as usual.
+pub fn analyze_revision(
+    path: &Path,
+    revision: &str,
+    options: &AnalysisOptions,
+) -> Result<(ProjectStructure, CommitInfo)> {
+    let repo = open(path)?;
+    let commit = resolve(&repo, revision)?;
+    let info = commit_info(&commit);
+
+    let mut checkout = Checkout::new(&repo, path)?;
+    checkout.switch(&commit)?;
+    Ok((checkout.analyze(path, &info, options)?, info))
+}
+
+// Analyzes the project at each of `commits` (as listed, newest first) from
+// the oldest on, calling `visit` with every result. Only the files that
+// differ from the previous commit are written and, for analyzers that allow
+// it, parsed again; a commit whose project directory has the same tree as
+// the one before gets that result again. Commits that fail to analyze are
+// reported and skipped.
+pub fn analyze_commits(
+    path: &Path,
+    commits: &[CommitInfo],
+    options: &AnalysisOptions,
+    mut visit: impl FnMut(&CommitInfo, &ProjectStructure),
+) -> Result<()> {
+    let repo = open(path)?;
+    let mut checkout = Checkout::new(&repo, path)?;
+    let mut previous: Option<(Oid, ProjectStructure)> = None;
+
+    for info in commits.iter().rev() {
+        let commit = resolve(&repo, &info.id)?;
+        let project_tree = checkout.project_tree(&commit)?;
+        if let Some((tree, structure)) = &previous {
+            if *tree == project_tree {
+                visit(info, structure);
+                continue;
+            }
+        }
+
+        checkout.switch(&commit)?;
+        match checkout.analyze(path, info, options) {
+            Ok(structure) => {
+                visit(info, &structure);
+                previous = Some((project_tree, structure));
+            }
+            Err(e) => {
+                eprintln!("Skipping {}: {:#}", info.short_id, e);
+                previous = None;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Splits `<directory>@<revision>`, as accepted wherever a diff side is
+pub fn parse_spec(spec: &str) -> Option<(&Path, &str)> {
+    spec.match_indices('@')
+        .map(|(i, _)| (Path::new(&spec[..i]), &spec[i + 1..]))
+        .find(|(dir, revision)| dir.is_dir() && !revision.is_empty())
+}
+
+fn open(path: &Path) -> Result<Repository> {
+    Repository::discover(path)
+        .with_context(|| format!("{} is not inside a git repository", path.display()))
+}
+
+fn resolve<'r>(repo: &'r Repository, revision: &str) -> Result<Commit<'r>> {
+    repo.revparse_single(revision)
+        .and_then(|object| object.peel_to_commit())
+        .with_context(|| format!("Unknown revision: {}", revision))
+}
+
+// A temporary directory holding the files of one commit at a time. The whole
+// tree is checked out so that workspace manifests above a member crate are
+// there too.
+struct Checkout<'r> {
+    repo: &'r Repository,
+    dir: TempDir,
+    // The project's directory relative to the repository root
+    prefix: PathBuf,
+    tree: Option<Tree<'r>>,
+    // Parses of the files of the commits analyzed so far
+    cache: ParseCache,
+}
+
+impl<'r> Checkout<'r> {
+    fn new(repo: &'r Repository, path: &Path) -> Result<Self> {
+        let workdir = repo
+            .workdir()
+            .context("Bare repositories have no project to analyze")?
+            .canonicalize()?;
+        let project = path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", path.display()))?;
+        let prefix = project
+            .strip_prefix(&workdir)
+            .with_context(|| format!("{} is outside {}", project.display(), workdir.display()))?
+            .to_path_buf();
+
+        let dir = tempfile::Builder::new()
+            .prefix("rust-visualizer-")
+            .tempdir()
+            .context("Failed to create a temporary checkout")?;
+        Ok(Self {
+            repo,
+            dir,
+            prefix,
+            tree: None,
+            cache: ParseCache::default(),
+        })
+    }
+
+    // Id of the project's directory at `commit`, equal across commits that
+    // don't touch it
+    fn project_tree(&self, commit: &Commit) -> Result<Oid> {
+        let tree = commit.tree()?;
+        if self.prefix.as_os_str().is_empty() {
+            return Ok(tree.id());
+        }
+        Ok(tree.get_path(&self.prefix).map(|entry| entry.id()).unwrap_or_else(|_| Oid::zero()))
+    }
+
+    // Moves the checkout to `commit`, rewriting only the files that differ
+    // from the commit checked out before
+    fn switch(&mut self, commit: &Commit<'r>) -> Result<()> {
+        let tree = commit.tree()?;
+        let short_id = &commit.id().to_string()[..8];
+        match &self.tree {
+            None => write_tree(self.repo, &tree, self.dir.path()),
+            Some(current) => self.update(current, &tree),
+        }
+        .with_context(|| format!("Failed to check out {}", short_id))?;
+        self.tree = Some(tree);
+        Ok(())
+    }
+
+    fn update(&self, current: &Tree, target: &Tree) -> Result<()> {
+        let diff = self.repo.diff_tree_to_tree(Some(current), Some(target), None)?;
+        for delta in diff.deltas() {
+            if let Some(old) = delta.old_file().path() {
+                if delta.status() != Delta::Added {
+                    let _ = fs::remove_file(self.dir.path().join(old));
+                }
+            }
+            let new = delta.new_file();
+            let Some(path) = new.path() else {
+                continue;
+            };
+            if delta.status() == Delta::Deleted
+                || !matches!(new.mode(), FileMode::Blob | FileMode::BlobExecutable)
+            {
+                continue;
+            }
+            let target = self.dir.path().join(path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let blob = self.repo.find_blob(new.id())?;
+            fs::write(&target, blob.content())
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+        }
+        Ok(())
+    }
+
+    fn analyze(&mut self, path: &Path, commit: &CommitInfo, options: &AnalysisOptions) -> Result<ProjectStructure> {
+        let root = self.dir.path().join(&self.prefix);
+        if !root.is_dir() {
+            bail!("{} does not exist at {}", self.prefix.display(), commit.short_id);
+        }
+
+        eprintln!("Analyzing {} at {} ({})", path.display(), commit.short_id, commit.summary);
+        let mut structure = language::analyze_project_cached(&root, options, &mut self.cache)?;
+        relocate(&mut structure, &root, path);
+        Ok(structure)
+    }
+}
+
+fn commit_info(commit: &Commit) -> CommitInfo {
+    let id = commit.id().to_string();
+    CommitInfo {
+        short_id: id[..8].to_string(),
+        id,
+        summary: commit.summary().unwrap_or_default().to_string(),
+        author: commit.author().name().unwrap_or_default().to_string(),
+        time: chrono::DateTime::from_timestamp(commit.time().seconds(), 0).unwrap_or_default(),
+    }
+}
+
+fn write_tree(repo: &Repository, tree: &Tree, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    for entry in tree.iter() {
+        let Some(name) = entry.name() else {
+            continue;
+        };
+        let target = dir.join(name);
+        // Submodules are commits of other repositories and are left out
+        match entry.kind() {
+            Some(ObjectType::Tree) => {
+                write_tree(repo, &entry.to_object(repo)?.peel_to_tree()?, &target)?
+            }
+            Some(ObjectType::Blob) if entry.filemode() != SYMLINK_MODE => {
+                let blob = entry.to_object(repo)?.peel_to_blob()?;
+                fs::write(&target, blob.content())
+                    .with_context(|| format!("Failed to write {}", target.display()))?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// Points paths from the temporary checkout back at the project, made
+// absolute like the paths of a working-tree analysis
+fn relocate(structure: &mut ProjectStructure, checkout: &Path, project: &Path) {
+    let project = project.canonicalize().unwrap_or_else(|_| project.to_path_buf());
+    structure.root_path = project.display().to_string();
+    for module in &mut structure.modules {
+        for path in std::iter::once(&mut module.path).chain(&mut module.files) {
+            // Compared by component, as `checkout` ends in a separator when
+            // the project is the repository root
+            if let Ok(rest) = Path::new(path.as_str()).strip_prefix(checkout) {
+                *path = project.join(rest).display().to_string();
+            }
+        }
+    }
+}
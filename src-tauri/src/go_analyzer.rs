@@ -0,0 +1,375 @@
+use crate::language::{FileState, LanguageAnalyzer, ParsedFile};
+use crate::models::*;
+use crate::walker::SourceWalker;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// Go modules are analyzed per package: every directory with .go files is one
+// `Module`, named by its import path
+pub struct GoAnalyzer {
+    root_path: PathBuf,
+    module_path: String,
+    requires: Vec<String>,
+    // Package directory -> the .go files the walker found in it
+    packages: BTreeMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl GoAnalyzer {
+    pub fn new(root_path: PathBuf, _options: AnalysisOptions) -> Self {
+        Self {
+            root_path,
+            module_path: String::new(),
+            requires: Vec::new(),
+            packages: BTreeMap::new(),
+        }
+    }
+
+    fn module_id(import_path: &str) -> String {
+        import_path
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    fn import_path(&self, dir: &Path) -> String {
+        let relative = dir.strip_prefix(&self.root_path).unwrap_or(dir);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if relative.is_empty() {
+            self.module_path.clone()
+        } else {
+            format!("{}/{}", self.module_path, relative)
+        }
+    }
+
+    // Directories the go tool never treats as packages of this module
+    fn is_package_dir(&self, dir: &Path) -> bool {
+        let relative = dir.strip_prefix(&self.root_path).unwrap_or(dir);
+        !relative.components().any(|c| {
+            let name = c.as_os_str().to_string_lossy();
+            name == "vendor" || name == "testdata" || name.starts_with('_') || name.starts_with('.')
+        })
+    }
+
+    fn parse_go_mod(&mut self, content: &str) -> Vec<Dependency> {
+        let mut dependencies = Vec::new();
+        let mut in_require = false;
+
+        for line in content.lines() {
+            let line = line.split("//").next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(module) = line.strip_prefix("module ") {
+                self.module_path = module.trim().trim_matches('"').to_string();
+                continue;
+            }
+
+            let entry = if in_require {
+                if line == ")" {
+                    in_require = false;
+                    continue;
+                }
+                line
+            } else if let Some(rest) = line.strip_prefix("require") {
+                let rest = rest.trim();
+                if rest == "(" {
+                    in_require = true;
+                    continue;
+                }
+                rest
+            } else {
+                continue;
+            };
+
+            let mut parts = entry.split_whitespace();
+            if let (Some(name), Some(version)) = (parts.next(), parts.next()) {
+                let name = name.trim_matches('"').to_string();
+                self.requires.push(name.clone());
+                dependencies.push(Dependency {
+                    name,
+                    version: version.to_string(),
+                    dep_type: DependencyType::Normal,
+                });
+            }
+        }
+
+        dependencies
+    }
+
+    fn extract_imports(content: &str) -> Vec<String> {
+        static SINGLE: OnceLock<Regex> = OnceLock::new();
+        static BLOCK: OnceLock<Regex> = OnceLock::new();
+        static QUOTED: OnceLock<Regex> = OnceLock::new();
+        let single = SINGLE
+            .get_or_init(|| Regex::new(r#"(?m)^import\s+(?:[\w.]+\s+)?"([^"]+)""#).expect("valid import regex"));
+        let block = BLOCK.get_or_init(|| Regex::new(r"(?ms)^import\s*\((.*?)^\)").expect("valid import block regex"));
+        let quoted = QUOTED.get_or_init(|| Regex::new(r#""([^"]+)""#).expect("valid quoted regex"));
+
+        let mut imports: Vec<String> = single.captures_iter(content).map(|c| c[1].to_string()).collect();
+        for caps in block.captures_iter(content) {
+            for line in caps[1].lines() {
+                let line = line.split("//").next().unwrap_or("");
+                if let Some(path) = quoted.captures(line) {
+                    imports.push(path[1].to_string());
+                }
+            }
+        }
+        imports
+    }
+
+    fn extract_items(content: &str) -> Vec<Item> {
+        static FUNC: OnceLock<Regex> = OnceLock::new();
+        static DECL: OnceLock<Regex> = OnceLock::new();
+        static BLOCK_DECL: OnceLock<Regex> = OnceLock::new();
+        let func = FUNC.get_or_init(|| {
+            Regex::new(r"(?m)^func\s+(?:\(\s*(?:\w+\s+)?\*?(\w+)(?:\[[^\]]*\])?\s*\)\s*)?(\w+)").expect("valid func regex")
+        });
+        let decl = DECL.get_or_init(|| {
+            Regex::new(r"(?m)^(type|const|var)\s+(\w+)(?:\[[^\]]*\])?\s*(struct|interface)?").expect("valid decl regex")
+        });
+        let block_decl = BLOCK_DECL
+            .get_or_init(|| Regex::new(r"(?ms)^(type|const|var)\s*\((.*?)^\)").expect("valid block decl regex"));
+
+        let mut found: Vec<(usize, Item)> = Vec::new();
+        let mut push = |offset: usize, name: &str, exported: bool, item_type: ItemType| {
+            found.push((
+                offset,
+                Item {
+                    name: name.to_string(),
+                    item_type,
+                    visibility: if exported { Visibility::Public } else { Visibility::Private },
+                    bases: Vec::new(),
+                    binding: None,
+                    span: None,
+                    docs: None,
+                    signature: None,
+                    fields: Vec::new(),
+                    effective_visibility: None,
+                    non_exhaustive: false,
+                },
+            ));
+        };
+        let is_exported = |name: &str| name.starts_with(|c: char| c.is_uppercase());
+
+        for caps in func.captures_iter(content) {
+            let offset = caps.get(0).map(|m| m.start()).unwrap_or(0);
+            let name = &caps[2];
+            match caps.get(1) {
+                // Methods are only reachable from outside through an exported receiver type
+                Some(receiver) => {
+                    let receiver = receiver.as_str();
+                    let exported = is_exported(receiver) && is_exported(name);
+                    push(offset, &format!("{}.{}", receiver, name), exported, ItemType::Function);
+                }
+                None if name == "init" || name == "_" => {}
+                None => push(offset, name, is_exported(name), ItemType::Function),
+            }
+        }
+
+        let decl_type = |keyword: &str, kind: Option<&str>| match (keyword, kind) {
+            ("type", Some("struct")) => ItemType::Struct,
+            ("type", Some("interface")) => ItemType::Trait,
+            ("type", _) => ItemType::Type,
+            ("const", _) => ItemType::Const,
+            _ => ItemType::Static,
+        };
+
+        for caps in decl.captures_iter(content) {
+            let offset = caps.get(0).map(|m| m.start()).unwrap_or(0);
+            let name = &caps[2];
+            if name != "_" {
+                push(offset, name, is_exported(name), decl_type(&caps[1], caps.get(3).map(|m| m.as_str())));
+            }
+        }
+
+        // `type ( ... )`, `const ( ... )` and `var ( ... )` groups
+        for caps in block_decl.captures_iter(content) {
+            let offset = caps.get(0).map(|m| m.start()).unwrap_or(0);
+            for line in caps[2].lines() {
+                // Only the group's own entries, not fields of nested structs
+                if !line.starts_with('\t') || line.starts_with("\t\t") {
+                    continue;
+                }
+                let mut parts = line.split_whitespace();
+                let Some(name) = parts.next().map(|n| n.trim_end_matches(',')) else {
+                    continue;
+                };
+                if name == "_" || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let kind = parts.find(|p| *p == "struct" || *p == "interface" || p.starts_with("struct{"));
+                let kind = kind.map(|k| if k.starts_with("struct") { "struct" } else { k });
+                push(offset, name, is_exported(name), decl_type(&caps[1], kind));
+            }
+        }
+
+        found.sort_by_key(|(offset, _)| *offset);
+        found.into_iter().map(|(_, item)| item).collect()
+    }
+
+    // In-module packages resolve to their module, everything else to the
+    // required module providing it, or to the standard library package
+    fn resolve_import(&self, import: &str) -> (String, Option<String>) {
+        if !self.module_path.is_empty()
+            && (import == self.module_path || import.starts_with(&format!("{}/", self.module_path)))
+        {
+            return (Self::module_id(import), None);
+        }
+
+        let provider = self
+            .requires
+            .iter()
+            .filter(|r| import == r.as_str() || import.starts_with(&format!("{}/", r)))
+            .max_by_key(|r| r.len());
+        match provider {
+            Some(module) => (Self::module_id(module), Some(module.clone())),
+            None => (Self::module_id(import), Some(import.to_string())),
+        }
+    }
+}
+
+impl LanguageAnalyzer for GoAnalyzer {
+    fn language(&self) -> Language {
+        Language::Go
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["go"]
+    }
+
+    fn detect(&self) -> bool {
+        self.root_path.join("go.mod").exists()
+    }
+
+    fn parse_dependencies(&mut self) -> Result<Vec<Dependency>> {
+        let content = fs::read_to_string(self.root_path.join("go.mod")).context("Failed to read go.mod")?;
+        Ok(self.parse_go_mod(&content))
+    }
+
+    fn discover_files(&mut self, walker: &mut SourceWalker) -> Vec<PathBuf> {
+        self.packages.clear();
+        for file in walker.files(&self.root_path) {
+            let Some(dir) = file.parent().filter(|dir| self.is_package_dir(dir)) else {
+                continue;
+            };
+            self.packages.entry(dir.to_path_buf()).or_default().push(file);
+        }
+
+        // One entry per package: its first source file, or first test file
+        // for a test-only package
+        self.packages
+            .values_mut()
+            .filter_map(|files| {
+                files.sort();
+                let first_source = files.iter().find(|p| !p.to_string_lossy().ends_with("_test.go"));
+                first_source.or(files.first()).cloned()
+            })
+            .collect()
+    }
+
+    fn parse_file(&mut self, path: &Path) -> Result<ParsedFile> {
+        let dir = path.parent().unwrap_or(&self.root_path).to_path_buf();
+        let files = self.packages.get(&dir).cloned().unwrap_or_else(|| vec![path.to_path_buf()]);
+
+        // Tests don't contribute to the package's API or import graph
+        let (tests, sources): (Vec<PathBuf>, Vec<PathBuf>) = files
+            .into_iter()
+            .partition(|p| p.to_string_lossy().ends_with("_test.go"));
+
+        let import_path = self.import_path(&dir);
+        let id = Self::module_id(&import_path);
+
+        let mut items = Vec::new();
+        let mut relationships: Vec<Relationship> = Vec::new();
+        let mut is_main = false;
+        for source in &sources {
+            let content = fs::read_to_string(source)
+                .with_context(|| format!("Failed to read file: {}", source.display()))?;
+            is_main |= content.lines().any(|l| l.trim() == "package main");
+            items.extend(Self::extract_items(&content));
+
+            for import in Self::extract_imports(&content) {
+                let (to, label) = self.resolve_import(&import);
+                if to != id && !relationships.iter().any(|r| r.to == to) {
+                    relationships.push(Relationship {
+                        from: id.clone(),
+                        to,
+                        rel_type: RelationType::Uses,
+                        label,
+                    });
+                }
+            }
+        }
+
+        let module_type = if sources.is_empty() && !tests.is_empty() {
+            ModuleType::Test
+        } else if is_main {
+            ModuleType::Binary
+        } else if dir == self.root_path {
+            ModuleType::Library
+        } else if import_path.contains("/examples/") {
+            ModuleType::Example
+        } else {
+            ModuleType::Module
+        };
+
+        // `internal` packages are importable only from their parent's subtree
+        let visibility = if import_path.split('/').any(|s| s == "internal") {
+            Visibility::Crate
+        } else {
+            Visibility::Public
+        };
+
+        let module = Module {
+            id,
+            name: import_path,
+            path: path.display().to_string(),
+            module_type,
+            visibility,
+            items,
+            entry_points: Vec::new(),
+            language: Language::Go,
+            invokes: Vec::new(),
+            impls: Vec::new(),
+            package: None,
+            docs: None,
+            reexports: Vec::new(),
+            lines_of_code: None,
+            churn: None,
+            owners: Vec::new(),
+            primary_authors: Vec::new(),
+            files: sources.iter().map(|p| p.display().to_string()).collect(),
+        };
+
+        Ok(ParsedFile {
+            module,
+            relationships,
+            nested_modules: Vec::new(),
+            state: FileState::default(),
+        })
+    }
+
+    fn build_relationships(&mut self, modules: &[Module]) -> Vec<Relationship> {
+        let mut relationships = Vec::new();
+        for module in modules {
+            let Some((parent_name, _)) = module.name.rsplit_once('/') else {
+                continue;
+            };
+            let parent_id = Self::module_id(parent_name);
+            if modules.iter().any(|m| m.id == parent_id) {
+                relationships.push(Relationship {
+                    from: parent_id,
+                    to: module.id.clone(),
+                    rel_type: RelationType::Declares,
+                    label: None,
+                });
+            }
+        }
+        relationships
+    }
+}
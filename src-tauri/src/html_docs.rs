@@ -0,0 +1,562 @@
+use crate::analyzer::{self, ProjectAnalyzer, HIGH_COUPLING_DEPS, LARGE_MODULE_LINES};
+use crate::models::*;
+use anyhow::{Context, Result};
+use layout::backends::svg::SVGWriter;
+use layout::core::base::Orientation;
+use layout::core::color::Color;
+use layout::core::style::{LineStyleKind, StyleAttr};
+use layout::std_shapes::render::get_shape_size;
+use layout::std_shapes::shapes::{Arrow, Element, LineEndKind, ShapeKind};
+use layout::topo::layout::VisualGraph;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Larger graphs take too long to lay out and are unreadable as one picture
+pub const MAX_GRAPH_MODULES: usize = 150;
+
+const STYLE: &str = r#"body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 0; color: #222; background: #fafafa; }
+header { background: #1e1e1e; color: #fff; padding: 0.8rem 2rem; display: flex; gap: 2rem; align-items: center; }
+header a { color: #fff; text-decoration: none; font-weight: 600; }
+main { padding: 1rem 2rem 3rem; max-width: 1200px; }
+a { color: #1565c0; }
+code, pre { font-family: "SFMono-Regular", Consolas, monospace; font-size: 0.9em; }
+pre.docs { white-space: pre-wrap; background: #f0f0f0; padding: 0.5rem; margin: 0.3rem 0; }
+table { border-collapse: collapse; width: 100%; margin: 0.5rem 0 1.5rem; background: #fff; }
+th, td { border: 1px solid #ddd; padding: 0.35rem 0.6rem; text-align: left; vertical-align: top; }
+th { background: #f0f0f0; }
+.stats { display: flex; gap: 2rem; }
+.problem { color: #c62828; }
+.graph { overflow: auto; border: 1px solid #ddd; background: #fff; max-height: 80vh; }
+.graph svg { max-width: none; }
+#search { padding: 0.4rem 0.8rem; width: 320px; border-radius: 6px; border: 1px solid #555; background: #333; color: #fff; }
+#search-results { position: absolute; top: 3rem; background: #fff; border: 1px solid #ccc; list-style: none; margin: 0; padding: 0; max-height: 60vh; overflow: auto; width: 420px; z-index: 10; }
+#search-results li { padding: 0.3rem 0.6rem; border-bottom: 1px solid #eee; }
+#search-results li span { color: #777; font-size: 0.85em; margin-left: 0.5rem; }
+"#;
+
+const SEARCH_SCRIPT: &str = r#"(function () {
+  var input = document.getElementById('search');
+  var results = document.getElementById('search-results');
+  if (!input || !results) return;
+  input.addEventListener('input', function () {
+    var query = input.value.trim().toLowerCase();
+    results.innerHTML = '';
+    if (!query) return;
+    SEARCH_INDEX.filter(function (entry) {
+      return entry.name.toLowerCase().indexOf(query) !== -1;
+    }).slice(0, 50).forEach(function (entry) {
+      var li = document.createElement('li');
+      var a = document.createElement('a');
+      a.href = SITE_ROOT + entry.page;
+      a.textContent = entry.name;
+      var kind = document.createElement('span');
+      kind.textContent = entry.kind + (entry.module ? ' in ' + entry.module : '');
+      li.appendChild(a);
+      li.appendChild(kind);
+      results.appendChild(li);
+    });
+  });
+})();
+"#;
+
+// Writes a self-contained site (no network access needed) into `output_dir`
+// and returns the path of its index page
+pub fn generate(structure: &ProjectStructure, output_dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(output_dir.join("modules"))
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+
+    let site = Site::new(structure);
+    write(&output_dir.join("style.css"), STYLE)?;
+    write(&output_dir.join("search.js"), &site.search_index())?;
+
+    let index = output_dir.join("index.html");
+    write(&index, &site.index_page())?;
+    for module in &structure.modules {
+        write(&output_dir.join(page_path(&module.id)), &site.module_page(module))?;
+    }
+    Ok(index)
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+struct Site<'a> {
+    structure: &'a ProjectStructure,
+    modules: HashMap<&'a str, &'a Module>,
+    metrics: HashMap<String, ModuleMetrics>,
+    problems: ProjectProblems,
+}
+
+impl<'a> Site<'a> {
+    fn new(structure: &'a ProjectStructure) -> Self {
+        Self {
+            structure,
+            modules: structure.modules.iter().map(|m| (m.id.as_str(), m)).collect(),
+            metrics: ProjectAnalyzer::new(structure.clone()).calculate_metrics(),
+            problems: analyzer::find_problems(structure),
+        }
+    }
+
+    fn project_name(&self) -> String {
+        Path::new(&self.structure.root_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.structure.root_path.clone())
+    }
+
+    // Loaded with a <script> tag rather than fetched, so it works from file://
+    fn search_index(&self) -> String {
+        let mut entries = Vec::new();
+        for module in &self.structure.modules {
+            entries.push(serde_json::json!({
+                "name": module.name,
+                "kind": "module",
+                "page": page_path(&module.id),
+            }));
+            for item in &module.items {
+                entries.push(serde_json::json!({
+                    "name": item.name,
+                    "kind": format!("{:?}", item.item_type).to_lowercase(),
+                    "module": module.name,
+                    "page": format!("{}#{}", page_path(&module.id), item_anchor(item)),
+                }));
+            }
+        }
+        format!(
+            "var SEARCH_INDEX = {};\n{}",
+            serde_json::Value::Array(entries),
+            SEARCH_SCRIPT
+        )
+    }
+
+    fn module_problems(&self, module: &Module) -> Vec<String> {
+        let mut problems = Vec::new();
+        for cycle in self.problems.cycles.iter().filter(|c| c.contains(&module.id)) {
+            // module_link escapes the names, so the joined links are safe HTML
+            let path: Vec<String> = cycle.iter().map(|id| self.module_link(id, "../")).collect();
+            problems.push(format!("Part of a dependency cycle: {}", path.join(" → ")));
+        }
+        if self.problems.unused_modules.contains(&module.name) {
+            problems.push("Not used by any other module".to_string());
+        }
+        if let Some(metric) = self.metrics.get(&module.id) {
+            if metric.lines_of_code > LARGE_MODULE_LINES {
+                problems.push(format!("Large module ({} lines)", metric.lines_of_code));
+            }
+            if metric.incoming_deps > HIGH_COUPLING_DEPS {
+                problems.push(format!("Highly coupled ({} incoming dependencies)", metric.incoming_deps));
+            }
+        }
+        let commands = module
+            .items
+            .iter()
+            .filter(|i| i.binding.as_ref().is_some_and(|b| b.kind == BindingKind::TauriCommand));
+        for item in commands {
+            if self.problems.unregistered_commands.contains(&item.name) {
+                problems.push(format!("Tauri command <code>{}</code> is not registered", escape(&item.name)));
+            }
+            if self.problems.uninvoked_commands.contains(&item.name) {
+                problems.push(format!("Tauri command <code>{}</code> is never invoked", escape(&item.name)));
+            }
+        }
+        problems
+    }
+
+    // A link to the module page, or the plain name for external targets,
+    // with the name HTML-escaped
+    fn module_link(&self, id: &str, root: &str) -> String {
+        match self.modules.get(id) {
+            Some(module) => format!(
+                "<a href=\"{}{}\">{}</a>",
+                root,
+                page_path(id),
+                escape(&module.name)
+            ),
+            None => format!("<code>{}</code>", escape(id)),
+        }
+    }
+
+    fn index_page(&self) -> String {
+        let structure = self.structure;
+        let mut body = String::new();
+        body.push_str(&format!("<h1>{}</h1>\n", escape(&self.project_name())));
+        body.push_str(&format!("<p><code>{}</code></p>\n", escape(&structure.root_path)));
+        body.push_str(&format!(
+            "<div class=\"stats\"><span>Modules: <strong>{}</strong></span><span>Dependencies: <strong>{}</strong></span><span>Relationships: <strong>{}</strong></span></div>\n",
+            structure.modules.len(),
+            structure.dependencies.len(),
+            structure.relationships.len()
+        ));
+
+        body.push_str("<h2>Module Graph</h2>\n");
+        if structure.modules.len() <= MAX_GRAPH_MODULES {
+            body.push_str("<p>Solid arrows are <code>use</code> dependencies, grey dashed arrows point to declared submodules and green dashed arrows to modules whose items are re-exported.</p>\n");
+            body.push_str(&format!("<div class=\"graph\">{}</div>\n", render_graph(structure)));
+        } else {
+            body.push_str(&format!(
+                "<p>The project has more than {} modules; use the module pages and the dependency table instead.</p>\n",
+                MAX_GRAPH_MODULES
+            ));
+        }
+
+        body.push_str("<h2>Modules</h2>\n");
+        body.push_str("<table>\n<tr><th>Module</th><th>Language</th><th>Type</th><th>Lines</th><th>Incoming</th><th>Outgoing</th><th>Items</th><th>Problems</th></tr>\n");
+        for module in &structure.modules {
+            let metric = self.metrics.get(&module.id);
+            let problems = self.module_problems(module).len();
+            body.push_str(&format!(
+                "<tr><td>{}</td><td>{:?}</td><td>{:?}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td{}>{}</td></tr>\n",
+                self.module_link(&module.id, ""),
+                module.language,
+                module.module_type,
+                metric.map_or(0, |m| m.lines_of_code),
+                metric.map_or(0, |m| m.incoming_deps),
+                metric.map_or(0, |m| m.outgoing_deps),
+                module.items.len(),
+                if problems > 0 { " class=\"problem\"" } else { "" },
+                problems
+            ));
+        }
+        body.push_str("</table>\n");
+
+        if !structure.public_api.is_empty() {
+            body.push_str("<h2>Public API</h2>\n");
+            body.push_str("<p>Paths under which library items can be used from other crates.</p>\n");
+            body.push_str("<table>\n<tr><th>Path</th><th>Kind</th><th>Defined as</th><th>Defined in</th></tr>\n");
+            for public in &structure.public_api {
+                let definition = if public.definition == public.path {
+                    String::new()
+                } else {
+                    format!("<code>{}</code>", escape(&public.definition))
+                };
+                body.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{:?}</td><td>{}</td><td>{}</td></tr>\n",
+                    escape(&public.path),
+                    public.item_type,
+                    definition,
+                    self.module_link(&public.module_id, "")
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+
+        body.push_str("<h2>Module Dependencies</h2>\n");
+        body.push_str("<table>\n<tr><th>From</th><th>To</th><th>Relation</th><th>Label</th></tr>\n");
+        for rel in &structure.relationships {
+            body.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{:?}</td><td>{}</td></tr>\n",
+                self.module_link(&rel.from, ""),
+                self.module_link(&rel.to, ""),
+                rel.rel_type,
+                escape(rel.label.as_deref().unwrap_or(""))
+            ));
+        }
+        body.push_str("</table>\n");
+
+        if !structure.dependencies.is_empty() {
+            body.push_str("<h2>External Dependencies</h2>\n");
+            body.push_str("<table>\n<tr><th>Name</th><th>Version</th><th>Type</th></tr>\n");
+            for dep in &structure.dependencies {
+                body.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{:?}</td></tr>\n",
+                    escape(&dep.name),
+                    escape(&dep.version),
+                    dep.dep_type
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+
+        page(&self.project_name(), "", &body)
+    }
+
+    fn module_page(&self, module: &Module) -> String {
+        let mut body = String::new();
+        body.push_str(&format!("<h1>{}</h1>\n", escape(&module.name)));
+        if let Some(docs) = &module.docs {
+            body.push_str(&format!("<pre class=\"docs\">{}</pre>\n", escape(docs)));
+        }
+
+        body.push_str("<table>\n");
+        body.push_str(&format!("<tr><th>Path</th><td><code>{}</code></td></tr>\n", escape(&module.path)));
+        body.push_str(&format!("<tr><th>Language</th><td>{:?}</td></tr>\n", module.language));
+        body.push_str(&format!("<tr><th>Type</th><td>{:?}</td></tr>\n", module.module_type));
+        body.push_str(&format!("<tr><th>Visibility</th><td>{:?}</td></tr>\n", module.visibility));
+        if let Some(package) = &module.package {
+            body.push_str(&format!("<tr><th>Package</th><td>{}</td></tr>\n", escape(package)));
+        }
+        if !module.owners.is_empty() {
+            body.push_str(&format!("<tr><th>Owners</th><td>{}</td></tr>\n", escape(&module.owners.join(", "))));
+        }
+        if !module.primary_authors.is_empty() {
+            let authors: Vec<String> = module
+                .primary_authors
+                .iter()
+                .map(|a| format!("{} ({} lines)", escape(&a.name), a.lines))
+                .collect();
+            body.push_str(&format!("<tr><th>Primary authors</th><td>{}</td></tr>\n", authors.join(", ")));
+        }
+        if let Some(metric) = self.metrics.get(&module.id) {
+            body.push_str(&format!(
+                "<tr><th>Metrics</th><td>{} lines, {} incoming, {} outgoing, complexity {}</td></tr>\n",
+                metric.lines_of_code, metric.incoming_deps, metric.outgoing_deps, metric.complexity_score
+            ));
+            if let Some(churn) = &metric.churn {
+                body.push_str(&format!(
+                    "<tr><th>Churn</th><td>{} commits, +{} −{} lines, {} authors in the last {} days (hotspot score {:.2})</td></tr>\n",
+                    churn.commits, churn.lines_added, churn.lines_removed, churn.authors, churn.window_days, metric.hotspot_score
+                ));
+            }
+        }
+        let (documented, public) = analyzer::doc_coverage(module);
+        if let Some(percent) = (documented * 100).checked_div(public) {
+            body.push_str(&format!(
+                "<tr><th>Doc coverage</th><td>{}% ({}/{} public items)</td></tr>\n",
+                percent,
+                documented,
+                public
+            ));
+        }
+        body.push_str("</table>\n");
+
+        body.push_str("<h2>Problems</h2>\n");
+        let problems = self.module_problems(module);
+        if problems.is_empty() {
+            body.push_str("<p>No problems found.</p>\n");
+        } else {
+            body.push_str("<ul>\n");
+            for problem in problems {
+                body.push_str(&format!("<li class=\"problem\">{}</li>\n", problem));
+            }
+            body.push_str("</ul>\n");
+        }
+
+        if !module.items.is_empty() {
+            body.push_str("<h2>Items</h2>\n");
+            body.push_str("<table>\n<tr><th>Name</th><th>Kind</th><th>Visibility</th><th>Lines</th></tr>\n");
+            for item in &module.items {
+                body.push_str(&format!(
+                    "<tr id=\"{}\"><td><code>{}</code>{}</td><td>{:?}</td><td>{}</td><td>{}</td></tr>\n",
+                    item_anchor(item),
+                    escape(&item.name),
+                    docs_block(item),
+                    item.item_type,
+                    visibility_text(item),
+                    span_text(item.span)
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+
+        if !module.reexports.is_empty() {
+            body.push_str("<h2>Re-exports</h2>\n");
+            body.push_str("<table>\n<tr><th>Name</th><th>Visibility</th><th>Use path</th><th>Resolves to</th></tr>\n");
+            for reexport in &module.reexports {
+                body.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{:?}</td><td><code>{}</code></td><td>{}</td></tr>\n",
+                    escape(&reexport.name),
+                    reexport.visibility,
+                    escape(&reexport.path),
+                    reexport
+                        .target
+                        .as_deref()
+                        .map_or_else(|| "external".to_string(), |t| format!("<code>{}</code>", escape(t)))
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+
+        if !module.impls.is_empty() {
+            body.push_str("<h2>Implementations</h2>\n");
+            for block in &module.impls {
+                let title = match &block.trait_name {
+                    Some(trait_name) => format!("impl {} for {}", trait_name, block.self_type),
+                    None => format!("impl {}", block.self_type),
+                };
+                body.push_str(&format!("<h3><code>{}</code></h3>\n", escape(&title)));
+                body.push_str("<table>\n<tr><th>Method</th><th>Visibility</th><th>Lines</th></tr>\n");
+                for method in &block.methods {
+                    body.push_str(&format!(
+                        "<tr><td><code>{}</code>{}</td><td>{}</td><td>{}</td></tr>\n",
+                        escape(&method.name),
+                        docs_block(method),
+                        visibility_text(method),
+                        span_text(method.span)
+                    ));
+                }
+                body.push_str("</table>\n");
+            }
+        }
+
+        for (title, outgoing) in [("Outgoing Edges", true), ("Incoming Edges", false)] {
+            let edges: Vec<&Relationship> = self
+                .structure
+                .relationships
+                .iter()
+                .filter(|r| if outgoing { r.from == module.id } else { r.to == module.id })
+                .collect();
+            body.push_str(&format!("<h2>{}</h2>\n", title));
+            if edges.is_empty() {
+                body.push_str("<p>None.</p>\n");
+                continue;
+            }
+            body.push_str("<table>\n<tr><th>Module</th><th>Relation</th><th>Label</th></tr>\n");
+            for rel in edges {
+                let other = if outgoing { &rel.to } else { &rel.from };
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{:?}</td><td>{}</td></tr>\n",
+                    self.module_link(other, "../"),
+                    rel.rel_type,
+                    escape(rel.label.as_deref().unwrap_or(""))
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+
+        page(&module.name, "../", &body)
+    }
+}
+
+fn page(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{title}</title>
+<link rel=\"stylesheet\" href=\"{root}style.css\">
+</head>
+<body>
+<header>
+<a href=\"{root}index.html\">Project Structure</a>
+<div style=\"position: relative\">
+<input id=\"search\" type=\"search\" placeholder=\"Search modules and items...\" autocomplete=\"off\">
+<ul id=\"search-results\"></ul>
+</div>
+</header>
+<main>
+{body}</main>
+<script>var SITE_ROOT = \"{root}\";</script>
+<script src=\"{root}search.js\"></script>
+</body>
+</html>
+",
+        title = escape(title),
+        root = root,
+        body = body
+    )
+}
+
+// Module ids made safe as file names: ASCII alphanumerics and `_` are kept,
+// anything else becomes `-<hex>-` so distinct ids never share a page
+fn page_path(id: &str) -> String {
+    let mut name = String::new();
+    for c in id.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            name.push(c);
+        } else {
+            name.push_str(&format!("-{:x}-", c as u32));
+        }
+    }
+    format!("modules/{}.html", name)
+}
+
+fn item_anchor(item: &Item) -> String {
+    format!("{:?}-{}", item.item_type, item.name).to_lowercase()
+}
+
+// `Public`, or `Public (effectively Crate)` for a `pub` item outside the API
+fn visibility_text(item: &Item) -> String {
+    match &item.effective_visibility {
+        Some(effective) if *effective != item.visibility => {
+            format!("{:?} (effectively {:?})", item.visibility, effective)
+        }
+        _ => format!("{:?}", item.visibility),
+    }
+}
+
+fn span_text(span: Option<Span>) -> String {
+    match span {
+        Some(span) if span.start_line == span.end_line => format!("{}", span.start_line),
+        Some(span) => format!("{}–{}", span.start_line, span.end_line),
+        None => "—".to_string(),
+    }
+}
+
+// Signature / fields and doc comment shown under the item name
+fn docs_block(item: &Item) -> String {
+    let mut block = String::new();
+    let code: Vec<&str> = item.signature.iter().chain(&item.fields).map(|s| s.as_str()).collect();
+    if !code.is_empty() {
+        block.push_str(&format!("<pre><code>{}</code></pre>", escape(&code.join("\n"))));
+    }
+    if let Some(docs) = &item.docs {
+        block.push_str(&format!("<pre class=\"docs\">{}</pre>", escape(docs)));
+    }
+    block
+}
+
+// Pre-rendered with layout-rs so the page needs neither Graphviz nor a
+// JavaScript graph library
+fn render_graph(structure: &ProjectStructure) -> String {
+    if structure.modules.is_empty() {
+        return String::new();
+    }
+    let mut graph = VisualGraph::new(Orientation::LeftToRight);
+    let mut handles = HashMap::new();
+    for module in &structure.modules {
+        let fill = match module.module_type {
+            ModuleType::Binary => "#ffcdd2",
+            ModuleType::Library => "#bbdefb",
+            ModuleType::Module => "#e0e0e0",
+            ModuleType::Test => "#c8e6c9",
+            ModuleType::Example => "#fff9c4",
+            ModuleType::Benchmark => "#ffe0b2",
+        };
+        let shape = ShapeKind::new_box(&module.name);
+        let look = StyleAttr::new(Color::fast("#555555"), 1, Some(Color::fast(fill)), 4, 12);
+        let size = get_shape_size(Orientation::LeftToRight, &shape, look.font_size, false);
+        let handle = graph.add_node(Element::create(shape, look, Orientation::LeftToRight, size));
+        handles.insert(module.id.as_str(), handle);
+    }
+
+    let mut seen = HashSet::new();
+    for rel in &structure.relationships {
+        let (Some(&from), Some(&to)) = (handles.get(rel.from.as_str()), handles.get(rel.to.as_str())) else {
+            continue;
+        };
+        if from == to || !seen.insert((rel.from.as_str(), rel.to.as_str())) {
+            continue;
+        }
+        let (line_style, color) = match rel.rel_type {
+            RelationType::Uses => (LineStyleKind::Normal, "#1565c0"),
+            RelationType::Declares => (LineStyleKind::Dashed, "#9e9e9e"),
+            RelationType::Inherits => (LineStyleKind::Normal, "#2e7d32"),
+            RelationType::Binds | RelationType::Invokes => (LineStyleKind::Dotted, "#9c27b0"),
+            RelationType::ReExports => (LineStyleKind::Dashed, "#00897b"),
+        };
+        let look = StyleAttr::new(Color::fast(color), 1, None, 0, 10);
+        let arrow = Arrow::new(LineEndKind::None, LineEndKind::Arrow, line_style, "", &look, &None, &None);
+        graph.add_edge(arrow, from, to);
+    }
+
+    let mut svg = SVGWriter::new();
+    graph.do_it(false, false, false, &mut svg);
+    let svg = svg.finalize();
+    // Inline <svg> elements must not carry an XML declaration
+    match svg.find("<svg") {
+        Some(start) => svg[start..].to_string(),
+        None => svg,
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,238 @@
+use crate::analyzer::ProjectAnalyzer;
+use crate::c_analyzer::CAnalyzer;
+use crate::churn;
+use crate::go_analyzer::GoAnalyzer;
+use crate::models::*;
+use crate::ownership;
+use crate::public_api;
+use crate::pyo3_bindings;
+use crate::python_analyzer::PythonAnalyzer;
+use crate::rust_analyzer::RustAnalyzer;
+use crate::tauri_commands;
+use crate::typescript_analyzer::TypeScriptAnalyzer;
+use crate::walker::SourceWalker;
+use anyhow::{bail, Context, Result};
+use git2::{ObjectType, Oid};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+// A module parsed from one source file, together with the edges it
+// contributes on its own (imports)
+#[derive(Clone)]
+pub struct ParsedFile {
+    pub module: Module,
+    pub relationships: Vec<Relationship>,
+    // Modules defined inside the file, such as Rust's inline `mod name { ... }`
+    pub nested_modules: Vec<Module>,
+    pub state: FileState,
+}
+
+// What an analyzer keeps from a file for its passes over all modules. It
+// travels with the parse so that a reused parse can hand it back.
+#[derive(Clone, Default)]
+pub struct FileState {
+    // Rust: submodules each module declares, with the visibility given there
+    pub mod_decls: Vec<(String, Vec<(String, Visibility)>)>,
+    // Rust: names each module brings in with `use`
+    pub uses: Vec<(String, Vec<String>)>,
+    // Python: each module's imported names and the dotted paths they stand for
+    pub imports: Vec<(String, HashMap<String, String>)>,
+}
+
+// Parses kept from one analysis to the next, as when stepping through
+// commits, keyed by the analyzer's parse context, the file and its blob id.
+// Only what the latest analysis used is kept.
+#[derive(Default)]
+pub struct ParseCache {
+    entries: HashMap<(String, PathBuf, Oid), ParsedFile>,
+    used: HashMap<(String, PathBuf, Oid), ParsedFile>,
+}
+
+impl ParseCache {
+    // Reuses the parse of an unchanged file, or parses it
+    fn parse(&mut self, analyzer: &mut dyn LanguageAnalyzer, context: &str, path: &Path) -> Result<ParsedFile> {
+        // The id git gives the content, which is the blob's for a checkout
+        let Ok(blob) = Oid::hash_file(ObjectType::Blob, path) else {
+            return analyzer.parse_file(path);
+        };
+        let key = (context.to_string(), path.to_path_buf(), blob);
+        let parsed = match self.entries.remove(&key) {
+            Some(parsed) => parsed,
+            None => analyzer.parse_file(path)?,
+        };
+        self.used.insert(key, parsed.clone());
+        Ok(parsed)
+    }
+
+    fn finish(&mut self) {
+        self.entries = std::mem::take(&mut self.used);
+    }
+}
+
+// Everything needed to add a language: how to recognize a project, where its
+// dependencies and sources are, how to parse a file and how modules relate.
+pub trait LanguageAnalyzer {
+    fn language(&self) -> Language;
+
+    fn extensions(&self) -> &[&str];
+
+    fn detect(&self) -> bool;
+
+    fn parse_dependencies(&mut self) -> Result<Vec<Dependency>>;
+
+    // Manifests that parse_dependencies skipped instead of failing on
+    fn manifest_errors(&mut self) -> Vec<ManifestError> {
+        Vec::new()
+    }
+
+    fn discover_files(&mut self, walker: &mut SourceWalker) -> Vec<PathBuf>;
+
+    fn parse_file(&mut self, path: &Path) -> Result<ParsedFile>;
+
+    // What parse_file depends on besides the file's path and content, such as
+    // the workspace layout. None when it also looks at other source files
+    // (to resolve imports), as its results then can't be reused.
+    fn parse_context(&self) -> Option<String> {
+        None
+    }
+
+    // Takes in the state of a file that was parsed, or whose parse was reused
+    fn add_file(&mut self, _state: &FileState) {}
+
+    // Fills in what needs more than one file, such as a module's visibility
+    // when it is set where the parent declares it
+    fn resolve_modules(&mut self, _modules: &mut [Module]) {}
+
+    fn build_relationships(&mut self, modules: &[Module]) -> Vec<Relationship>;
+}
+
+type AnalyzerFactory = fn(PathBuf, AnalysisOptions) -> Box<dyn LanguageAnalyzer>;
+
+// Cross-language passes that run once every analyzer has contributed
+type Linker = fn(&mut ProjectStructure, &Path);
+
+fn analyzers() -> Vec<AnalyzerFactory> {
+    vec![
+        |root, options| Box::new(RustAnalyzer::new(root, options)),
+        |root, options| Box::new(PythonAnalyzer::new(root, options)),
+        |root, options| Box::new(TypeScriptAnalyzer::new(root, options)),
+        |root, options| Box::new(GoAnalyzer::new(root, options)),
+        |root, options| Box::new(CAnalyzer::new(root, options)),
+    ]
+}
+
+fn linkers() -> Vec<Linker> {
+    vec![
+        pyo3_bindings::link_bindings,
+        tauri_commands::link_commands,
+        public_api::link_reexports,
+        public_api::link_public_api,
+    ]
+}
+
+// Runs every analyzer that recognizes the project and merges the results
+pub fn analyze_project(root_path: &Path, options: &AnalysisOptions) -> Result<ProjectStructure> {
+    analyze(root_path, options, None)
+}
+
+// Like analyze_project, reusing parses from the last analysis made with
+// `cache` for the files that haven't changed since
+pub fn analyze_project_cached(
+    root_path: &Path,
+    options: &AnalysisOptions,
+    cache: &mut ParseCache,
+) -> Result<ProjectStructure> {
+    let structure = analyze(root_path, options, Some(&mut *cache));
+    cache.finish();
+    structure
+}
+
+fn analyze(root_path: &Path, options: &AnalysisOptions, mut cache: Option<&mut ParseCache>) -> Result<ProjectStructure> {
+    eprintln!("Analyzing project at: {:?}", root_path);
+
+    let mut detected: Vec<Box<dyn LanguageAnalyzer>> = analyzers()
+        .into_iter()
+        .map(|factory| factory(root_path.to_path_buf(), options.clone()))
+        .filter(|analyzer| analyzer.detect())
+        .collect();
+
+    if detected.is_empty() {
+        bail!("No supported language detected");
+    }
+
+    let mut combined: Option<ProjectStructure> = None;
+    for analyzer in detected.iter_mut() {
+        let language = analyzer.language();
+        let structure = run_analyzer(analyzer.as_mut(), root_path, options, cache.as_deref_mut())
+            .with_context(|| format!("{:?} analysis failed", language))?;
+
+        match combined.as_mut() {
+            Some(combined) => combined.merge(structure),
+            None => combined = Some(structure),
+        }
+    }
+
+    let mut structure = combined.expect("at least one analyzer ran");
+    for link in linkers() {
+        link(&mut structure, root_path);
+    }
+    churn::link_churn(&mut structure, root_path, options);
+    ownership::link_owners(&mut structure, root_path, options);
+    Ok(structure)
+}
+
+fn run_analyzer(
+    analyzer: &mut dyn LanguageAnalyzer,
+    root_path: &Path,
+    options: &AnalysisOptions,
+    mut cache: Option<&mut ParseCache>,
+) -> Result<ProjectStructure> {
+    let dependencies = analyzer
+        .parse_dependencies()
+        .context("Failed to parse dependencies")?;
+
+    let mut walker = SourceWalker::new(root_path, analyzer.extensions(), options);
+    let files = analyzer.discover_files(&mut walker);
+
+    let context = analyzer.parse_context();
+    let mut modules = Vec::new();
+    let mut relationships = Vec::new();
+    for path in files {
+        let parsed = match (cache.as_deref_mut(), &context) {
+            (Some(cache), Some(context)) => cache.parse(analyzer, context, &path),
+            _ => analyzer.parse_file(&path),
+        };
+        match parsed {
+            Ok(parsed) => {
+                analyzer.add_file(&parsed.state);
+                modules.push(parsed.module);
+                modules.extend(parsed.nested_modules);
+                relationships.extend(parsed.relationships);
+            }
+            Err(e) => {
+                eprintln!("Failed to parse {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    analyzer.resolve_modules(&mut modules);
+    for module in &mut modules {
+        if module.lines_of_code.is_none() {
+            module.lines_of_code = Some(ProjectAnalyzer::count_lines(module));
+        }
+    }
+    relationships.extend(analyzer.build_relationships(&modules));
+
+    Ok(ProjectStructure {
+        root_path: root_path.display().to_string(),
+        modules,
+        dependencies,
+        relationships,
+        diagnostics: AnalysisDiagnostics {
+            skipped: walker.skipped(),
+            manifest_errors: analyzer.manifest_errors(),
+            codeowners: None,
+        },
+        public_api: Vec::new(),
+    })
+}
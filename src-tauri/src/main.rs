@@ -1,43 +1,49 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod analyzer;
+mod api_diff;
+mod c_analyzer;
+mod churn;
+mod cli;
+mod doc_template;
+mod export;
+mod git_history;
+mod go_analyzer;
+mod html_docs;
+mod language;
 mod models;
+mod ownership;
 mod parser;
 mod python_analyzer; 
+mod python_entry_points;
+mod python_manifest;
+mod public_api;
+mod pyo3_bindings;
+mod rust_analyzer;
+mod snapshot;
+mod structure_diff;
+mod tauri_commands;
+mod trend;
+mod typescript_analyzer;
+mod walker;
 
-use analyzer::ProjectAnalyzer;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
-use crate::models::{ProjectStructure, ModuleType, Visibility, DependencyType, ProjectProblems, ModuleMetrics, RelationType};
-use python_analyzer::PythonAnalyzer;
+use crate::analyzer::ProjectAnalyzer;
+use crate::models::{AnalysisOptions, ApiDiff, ExportOptions, ProjectStructure, ProjectProblems, Snapshot, StructureDiff, CommitInfo, TrendPoint, OwnershipReport, ModuleMetrics, RelationType};
 
 #[tauri::command]
-async fn analyze_project(path: String) -> Result<ProjectStructure, String> {
+async fn analyze_project(path: String, options: Option<AnalysisOptions>) -> Result<ProjectStructure, String> {
     let project_path = PathBuf::from(path);
+    let options = options.unwrap_or_default();
     
     if !project_path.exists() {
         return Err("Project path does not exist".to_string());
     }
-    
-    // Check Rust project
-    let cargo_toml = project_path.join("Cargo.toml");
-    if cargo_toml.exists() {
-        let mut analyzer = ProjectAnalyzer::new(project_path);
-        return analyzer.analyze()
-            .map_err(|e| format!("Rust analysis failed: {}", e));
-    }
-    
-    // Check Python project
-    let python_markers = ["setup.py", "requirements.txt", "pyproject.toml", "__init__.py"];
-    for marker in python_markers {
-        if project_path.join(marker).exists() {
-            let mut analyzer = PythonAnalyzer::new(project_path);
-            return analyzer.analyze()
-                .map_err(|e| format!("Python analysis failed: {}", e));
-        }
-    }
-    
-    Err("Not a valid Rust or Python project".to_string())
+
+    language::analyze_project(&project_path, &options)
+        .map_err(|e| format!("{:#}", e))
 }
 
 
@@ -54,107 +60,16 @@ async fn save_file_content(path: String, content: String) -> Result<(), String>
 }
 
 #[tauri::command]
-async fn generate_documentation(structure: ProjectStructure) -> Result<String, String> {
+async fn generate_documentation(structure: ProjectStructure, template_path: Option<String>) -> Result<String, String> {
     let output_path = Path::new(&structure.root_path).join("PROJECT_STRUCTURE.md");
-    
-    let mut doc = String::new();
-    
-    // Header
-    doc.push_str(&format!("# 📦 Project Structure\n\n"));
-    doc.push_str(&format!("**Project:** `{}`\n\n", structure.root_path));
-    
-    // Statistics
-    doc.push_str("## 📊 Statistics\n\n");
-    doc.push_str(&format!("- **Total Modules:** {}\n", structure.modules.len()));
-    doc.push_str(&format!("- **Dependencies:** {}\n", structure.dependencies.len()));
-    doc.push_str(&format!("- **Relationships:** {}\n\n", structure.relationships.len()));
-    
-    let pub_count = structure.modules.iter().filter(|m| m.visibility == Visibility::Public).count();
-    let test_count = structure.modules.iter().filter(|m| m.module_type == ModuleType::Test).count();
-    
-    doc.push_str(&format!("- **Public Modules:** {}\n", pub_count));
-    doc.push_str(&format!("- **Tests:** {}\n\n", test_count));
-    
-    // Module Tree
-    doc.push_str("## Module Tree\n\n");
-    doc.push_str("```\n");
-    for module in &structure.modules {
-        let indent = module.name.matches("::").count();
-        let prefix = "  ".repeat(indent);
-        let icon = match module.module_type {
-            ModuleType::Binary => "🔷",
-            ModuleType::Library => "📚",
-            ModuleType::Test => "🧪",
-            ModuleType::Example => "📘",
-            ModuleType::Benchmark => "⚡",
-            _ => "📦",
-        };
-        doc.push_str(&format!("{}{}  {}\n", prefix, icon, module.name));
-    }
-    doc.push_str("```\n\n");
-    
-    // Dependencies
-    if !structure.dependencies.is_empty() {
-        doc.push_str("## Dependencies\n\n");
-        doc.push_str("| Crate | Version | Type |\n");
-        doc.push_str("|-------|---------|------|\n");
-        for dep in &structure.dependencies {
-            let dep_type = match dep.dep_type {
-                DependencyType::Normal => "Production",
-                DependencyType::Dev => "Development",
-                DependencyType::Build => "Build",
-            };
-            doc.push_str(&format!("| `{}` | {} | {} |\n", dep.name, dep.version, dep_type));
-        }
-        doc.push_str("\n");
-    }
-    
-    // Modules Detail
-    doc.push_str("## Modules Detail\n\n");
-    for module in &structure.modules {
-        doc.push_str(&format!("### {} `{}`\n\n", 
-            match module.module_type {
-                ModuleType::Binary => "",
-                ModuleType::Library => "",
-                ModuleType::Test => "",
-                ModuleType::Example => "",
-                ModuleType::Benchmark => "⚡",
-                _ => "",
-            },
-            module.name
-        ));
-        
-        doc.push_str(&format!("- **Path:** `{}`\n", module.path));
-        doc.push_str(&format!("- **Visibility:** {:?}\n", module.visibility));
-        doc.push_str(&format!("- **Items:** {}\n\n", module.items.len()));
-        
-        if !module.items.is_empty() {
-            doc.push_str("**Exported Items:**\n\n");
-            for item in &module.items {
-                let visibility = match item.visibility {
-                    Visibility::Public => "pub",
-                    _ => "priv",
-                };
-                doc.push_str(&format!("- `{}` **{:?}** `{}`\n", 
-                    visibility, item.item_type, item.name));
-            }
-            doc.push_str("\n");
-        }
-    }
-    
-    // Module Graph
-    doc.push_str("##Module Dependencies\n\n");
-    doc.push_str("```mermaid\ngraph TD\n");
-    for rel in &structure.relationships {
-        doc.push_str(&format!("    {}[{}] --> {}[{}]\n", 
-            rel.from.replace("_", ""), rel.from,
-            rel.to.replace("_", ""), rel.to
-        ));
-    }
-    doc.push_str("```\n\n");
-    
-    doc.push_str("---\n");
-    doc.push_str(&format!("*Generated by Rust Project Visualizer*\n"));
+
+    let template = match &template_path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read template: {}", e))?,
+        None => doc_template::DEFAULT_TEMPLATE.to_string(),
+    };
+    let doc = doc_template::render(&structure, &template)
+        .map_err(|e| format!("{:#}", e))?;
     
     fs::write(&output_path, doc)
         .map_err(|e| format!("Failed to write documentation: {}", e))?;
@@ -163,46 +78,131 @@ async fn generate_documentation(structure: ProjectStructure) -> Result<String, S
 }
 
 #[tauri::command]
-async fn analyze_problems(structure: ProjectStructure) -> Result<ProjectProblems, String> {
-    let mut analyzer = ProjectAnalyzer::new(PathBuf::from(&structure.root_path));
-    analyzer.initialize_data(structure.clone());
+async fn generate_html_docs(structure: ProjectStructure, output_dir: String) -> Result<String, String> {
+    html_docs::generate(&structure, Path::new(&output_dir))
+        .map(|index| index.display().to_string())
+        .map_err(|e| format!("{:#}", e))
+}
 
-    let cycles = analyzer.detect_cycles();
-    let unused = analyzer.find_unused_modules();
-    let metrics = analyzer.calculate_metrics();
-    
-    let mut large_modules = Vec::new();
-    let mut highly_coupled = Vec::new();
-    
-    for (id, metric) in &metrics {
-        if metric.lines_of_code > 500 {
-            if let Some(module) = structure.modules.iter().find(|m| &m.id == id) {
-                large_modules.push(format!("{} ({} lines)", module.name, metric.lines_of_code));
-            }
-        }
-        if metric.incoming_deps > 10 {
-            if let Some(module) = structure.modules.iter().find(|m| &m.id == id) {
-                highly_coupled.push(format!("{} ({} deps)", module.name, metric.incoming_deps));
-            }
-        }
+#[tauri::command]
+async fn save_snapshot(structure: ProjectStructure, path: String) -> Result<String, String> {
+    snapshot::save(&structure, Path::new(&path))
+        .map(|_| path)
+        .map_err(|e| format!("{:#}", e))
+}
+
+#[tauri::command]
+async fn load_snapshot(path: String) -> Result<Snapshot, String> {
+    snapshot::load(Path::new(&path))
+        .map_err(|e| format!("{:#}", e))
+}
+
+// Compares the public API of two project directories or saved analyses
+#[tauri::command]
+async fn diff_public_api(old_path: String, new_path: String, options: Option<AnalysisOptions>) -> Result<ApiDiff, String> {
+    let options = options.unwrap_or_default();
+    let old = snapshot::load_structure(&old_path, &options)
+        .map_err(|e| format!("{:#}", e))?;
+    let new = snapshot::load_structure(&new_path, &options)
+        .map_err(|e| format!("{:#}", e))?;
+    Ok(api_diff::diff(&old, &new))
+}
+
+#[tauri::command]
+async fn list_commits(path: String, limit: Option<usize>) -> Result<Vec<CommitInfo>, String> {
+    git_history::list_commits(Path::new(&path), None, limit.unwrap_or(50))
+        .map_err(|e| format!("{:#}", e))
+}
+
+// The project as it was at a commit, as a snapshot recording that commit
+#[tauri::command]
+async fn analyze_revision(path: String, revision: String, options: Option<AnalysisOptions>) -> Result<Snapshot, String> {
+    let (structure, commit) = git_history::analyze_revision(Path::new(&path), &revision, &options.unwrap_or_default())
+        .map_err(|e| format!("{:#}", e))?;
+    Ok(snapshot::create(structure, Some(commit.id)))
+}
+
+// Aggregates over the last `limit` commits (every `step`-th), oldest first
+#[tauri::command]
+async fn analyze_trend(path: String, limit: Option<usize>, step: Option<usize>, options: Option<AnalysisOptions>) -> Result<Vec<TrendPoint>, String> {
+    trend::collect(Path::new(&path), None, limit.unwrap_or(20), step.unwrap_or(1), &options.unwrap_or_default())
+        .map_err(|e| format!("{:#}", e))
+}
+
+#[tauri::command]
+async fn export_trend(points: Vec<TrendPoint>, format: String) -> Result<String, String> {
+    match format.as_str() {
+        "csv" => Ok(trend::to_csv(&points)),
+        "json" => serde_json::to_string_pretty(&points).map_err(|e| e.to_string()),
+        other => Err(format!("Unsupported format: {}", other)),
     }
-    
-    Ok(ProjectProblems {
-        cycles,
-        unused_modules: unused,
-        large_modules,
-        highly_coupled,
-    })
+}
+
+// Changes from `old` to `new`, with a merged graph for highlighting
+#[tauri::command]
+async fn diff_structures(old: ProjectStructure, new: ProjectStructure) -> Result<StructureDiff, String> {
+    Ok(structure_diff::diff(&old, &new))
+}
+
+#[tauri::command]
+async fn analyze_ownership(structure: ProjectStructure) -> Result<OwnershipReport, String> {
+    Ok(ownership::report(&structure))
+}
+
+// Adds git churn to an analysis, for the hotspot view
+#[tauri::command]
+async fn link_churn(mut structure: ProjectStructure, days: Option<u32>) -> Result<ProjectStructure, String> {
+    let options = AnalysisOptions {
+        churn_days: Some(days.unwrap_or(churn::DEFAULT_CHURN_DAYS)),
+        ..AnalysisOptions::default()
+    };
+    let root_path = PathBuf::from(&structure.root_path);
+    churn::link_churn(&mut structure, &root_path, &options);
+    Ok(structure)
+}
+
+// Per-module metrics, including churn and hotspot scores
+#[tauri::command]
+async fn calculate_metrics(structure: ProjectStructure) -> Result<HashMap<String, ModuleMetrics>, String> {
+    Ok(ProjectAnalyzer::new(structure).calculate_metrics())
+}
+
+#[tauri::command]
+async fn analyze_problems(structure: ProjectStructure) -> Result<ProjectProblems, String> {
+    Ok(analyzer::find_problems(&structure))
+}
+
+#[tauri::command]
+async fn export_graph(structure: ProjectStructure, options: Option<ExportOptions>) -> Result<String, String> {
+    Ok(export::export(&structure, &options.unwrap_or_default()))
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
             analyze_project,
             read_file_content,
             save_file_content,
             generate_documentation,
-            analyze_problems
+            generate_html_docs,
+            save_snapshot,
+            load_snapshot,
+            list_commits,
+            analyze_revision,
+            analyze_trend,
+            export_trend,
+            diff_public_api,
+            diff_structures,
+            link_churn,
+            calculate_metrics,
+            analyze_ownership,
+            analyze_problems,
+            export_graph
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
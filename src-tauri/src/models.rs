@@ -6,6 +6,11 @@ pub struct ProjectStructure {
     pub modules: Vec<Module>,
     pub dependencies: Vec<Dependency>,
     pub relationships: Vec<Relationship>,
+    #[serde(default)]
+    pub diagnostics: AnalysisDiagnostics,
+    // Paths under which library items are reachable from outside their crate
+    #[serde(default)]
+    pub public_api: Vec<PublicPath>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +21,54 @@ pub struct Module {
     pub module_type: ModuleType,
     pub visibility: Visibility,
     pub items: Vec<Item>,
+    #[serde(default)]
+    pub entry_points: Vec<EntryPoint>,
+    #[serde(default)]
+    pub language: Language,
+    // Tauri commands called from this module through `invoke`
+    #[serde(default)]
+    pub invokes: Vec<String>,
+    #[serde(default)]
+    pub impls: Vec<ImplBlock>,
+    // Cargo package the module belongs to (workspace member)
+    #[serde(default)]
+    pub package: Option<String>,
+    // Module-level doc comment (`//!`)
+    #[serde(default)]
+    pub docs: Option<String>,
+    // `pub use` / `pub(crate) use` declarations
+    #[serde(default)]
+    pub reexports: Vec<ReExport>,
+    // Counted during analysis, so metrics stay right once the sources have
+    // changed or are gone (snapshots, historical revisions)
+    #[serde(default)]
+    pub lines_of_code: Option<usize>,
+    // Recent change activity; none outside a git repository
+    #[serde(default)]
+    pub churn: Option<Churn>,
+    // Owners assigned by CODEOWNERS
+    #[serde(default)]
+    pub owners: Vec<String>,
+    // Authors of most of the module's current lines (git blame, on request)
+    #[serde(default)]
+    pub primary_authors: Vec<Contributor>,
+    // Every source file of a module spanning several (a Go package), `path`
+    // being the first of them; empty when `path` is the only one
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Rust,
+    Python,
+    TypeScript,
+    JavaScript,
+    Go,
+    C,
+    Cpp,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,14 +93,96 @@ pub enum Visibility {
     Super,
 }
 
+// An `impl` block: inherent (`impl Foo`) or of a trait (`impl Display for Foo`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImplBlock {
+    pub self_type: String,
+    pub trait_name: Option<String>,
+    pub methods: Vec<Item>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Item {
     pub name: String,
     pub item_type: ItemType,
     pub visibility: Visibility,
+    #[serde(default)]
+    pub bases: Vec<String>,
+    #[serde(default)]
+    pub binding: Option<Binding>,
+    #[serde(default)]
+    pub span: Option<Span>,
+    // Doc comment text (`///` or `#[doc = "..."]`)
+    #[serde(default)]
+    pub docs: Option<String>,
+    // Declaration as source text: a function's signature, a trait's header,
+    // or the type of a const, static or alias
+    #[serde(default)]
+    pub signature: Option<String>,
+    // Struct fields, enum variants or trait members as source text
+    #[serde(default)]
+    pub fields: Vec<String>,
+    // Public only if reachable from the crate root through `pub` modules and
+    // re-exports; `pub` items of private modules are effectively crate-visible
+    #[serde(default)]
+    pub effective_visibility: Option<Visibility>,
+    // `#[non_exhaustive]` structs and enums
+    #[serde(default)]
+    pub non_exhaustive: bool,
+}
+
+// 1-based, inclusive line range in the module's file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Span {
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+// A re-exporting `use`: `pub use inner::Thing as Alias` has path
+// `inner::Thing` and name `Alias`; glob imports have name `*`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReExport {
+    pub path: String,
+    pub name: String,
+    pub visibility: Visibility,
+    // Where the item is defined (`my_crate::inner::deep::Thing`); none for
+    // items of other crates
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicPath {
+    // Fully qualified, starting with the crate name: `my_crate::inner::Thing`
+    pub path: String,
+    pub item_type: ItemType,
+    // Where the item is defined; methods are named `Type::method`
+    pub module_id: String,
+    pub name: String,
+    // Path of the definition, differs from `path` for re-exported items
+    pub definition: String,
 }
 
+// How an item is exposed to another language, e.g. a PyO3 `#[pyfunction]`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Binding {
+    pub kind: BindingKind,
+    pub exported_name: String,
+    #[serde(default)]
+    pub exposes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingKind {
+    Pymodule,
+    Pyfunction,
+    Pyclass,
+    TauriCommand,
+    TauriHandler,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ItemType {
     Function,
@@ -58,6 +193,7 @@ pub enum ItemType {
     Static,
     Type,
     Macro,
+    Module,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +216,8 @@ pub struct Relationship {
     pub from: String,
     pub to: String,
     pub rel_type: RelationType,
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -87,6 +225,28 @@ pub struct Relationship {
 pub enum RelationType {
     Uses,
     Declares,
+    Inherits,
+    Binds,
+    Invokes,
+    // `pub use` of an item or module defined in the target module
+    ReExports,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryPoint {
+    pub kind: EntryPointKind,
+    pub framework: String,
+    pub name: String,
+    pub handler: Option<String>,
+    pub methods: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryPointKind {
+    HttpRoute,
+    CliCommand,
+    TestFixture,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,6 +255,194 @@ pub struct ModuleMetrics {
     pub incoming_deps: usize,
     pub outgoing_deps: usize,
     pub complexity_score: usize,
+    #[serde(default)]
+    pub churn: Option<Churn>,
+    // 0 to 1: how often the module changes times its size and complexity,
+    // relative to the rest of the project
+    #[serde(default)]
+    pub hotspot_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contributor {
+    pub name: String,
+    pub lines: usize,
+}
+
+// Who owns which modules, and where dependencies cross team boundaries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipReport {
+    pub codeowners: Option<String>,
+    pub teams: Vec<TeamModules>,
+    // `Uses` edges between modules that have owners but none in common
+    pub boundary_edges: Vec<BoundaryEdge>,
+    pub unowned_modules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamModules {
+    pub owner: String,
+    pub modules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoundaryEdge {
+    pub from: String,
+    pub to: String,
+    pub from_owners: Vec<String>,
+    pub to_owners: Vec<String>,
+}
+
+// Commits touching a module's files within the last `window_days` days
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Churn {
+    pub window_days: u32,
+    pub commits: usize,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    pub authors: usize,
+}
+
+// A saved analysis that can be reopened without the sources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub format_version: u32,
+    pub tool_version: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub git_commit: Option<String>,
+    pub structure: ProjectStructure,
+    pub problems: ProjectProblems,
+    pub metrics: std::collections::HashMap<String, ModuleMetrics>,
+}
+
+// A commit of the project's git repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitInfo {
+    pub id: String,
+    pub short_id: String,
+    pub summary: String,
+    pub author: String,
+    pub time: chrono::DateTime<chrono::Utc>,
+}
+
+// Architecture aggregates of the project at one commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub commit: CommitInfo,
+    pub modules: usize,
+    pub relationships: usize,
+    pub lines_of_code: usize,
+    // Mean of incoming + outgoing dependencies per module
+    pub average_coupling: f64,
+    pub largest_module: Option<String>,
+    pub largest_module_lines: usize,
+    pub cycles: usize,
+    pub unused_modules: usize,
+    pub large_modules: usize,
+    pub highly_coupled: usize,
+    pub unregistered_commands: usize,
+    pub uninvoked_commands: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiChange {
+    pub path: String,
+    pub kind: ApiChangeKind,
+    pub item_type: ItemType,
+    pub breaking: bool,
+    // What changed, one entry per difference
+    pub reasons: Vec<String>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiDiff {
+    pub changes: Vec<ApiChange>,
+    pub breaking: usize,
+    // "major", "minor" or "patch"
+    pub suggested_bump: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Moved,
+    Renamed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleChange {
+    pub kind: ChangeKind,
+    // Id in the new version, or in the old one for removed modules
+    pub id: String,
+    pub name: String,
+    // Set for moved and renamed modules
+    pub old_id: Option<String>,
+    pub old_name: Option<String>,
+    // Module-level differences: visibility, type, path
+    pub details: Vec<String>,
+    pub item_changes: Vec<ItemChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemChange {
+    pub kind: ChangeKind,
+    pub name: String,
+    pub item_type: ItemType,
+    pub details: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipChange {
+    pub kind: ChangeKind,
+    pub from: String,
+    pub to: String,
+    pub rel_type: RelationType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub module_id: String,
+    pub name: String,
+    pub lines_of_code: i64,
+    pub incoming_deps: i64,
+    pub outgoing_deps: i64,
+    pub complexity_score: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatDelta {
+    pub name: String,
+    pub before: usize,
+    pub after: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructureDiff {
+    pub totals: Vec<StatDelta>,
+    pub modules: Vec<ModuleChange>,
+    pub relationships: Vec<RelationshipChange>,
+    // Cycles are listed with module ids of the new version
+    pub new_cycles: Vec<Vec<String>>,
+    pub resolved_cycles: Vec<Vec<String>>,
+    pub metric_deltas: Vec<MetricDelta>,
+    // Both versions in one graph: the new structure plus removed modules
+    // and relationships, to be highlighted using `modules`/`relationships`
+    pub graph: ProjectStructure,
+    // Summary for a pull request comment
+    pub markdown: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,5 +451,140 @@ pub struct ProjectProblems {
     pub unused_modules: Vec<String>,
     pub large_modules: Vec<String>,
     pub highly_coupled: Vec<String>,
+    #[serde(default)]
+    pub unregistered_commands: Vec<String>,
+    #[serde(default)]
+    pub uninvoked_commands: Vec<String>,
+}
+
+impl Module {
+    // How the module's name is nested in its own language
+    pub fn name_separator(&self) -> &'static str {
+        match self.language {
+            Language::Rust => "::",
+            Language::Python => ".",
+            _ => "/",
+        }
+    }
+
+    pub fn name_segments(&self) -> Vec<&str> {
+        self.name.split(self.name_separator()).collect()
+    }
+
+    pub fn source_files(&self) -> Vec<&str> {
+        match self.files.is_empty() {
+            true => vec![self.path.as_str()],
+            false => self.files.iter().map(String::as_str).collect(),
+        }
+    }
 }
 
+impl ProjectStructure {
+    // Folds another language's analysis of the same root into this one,
+    // renaming colliding module ids so both graphs stay intact
+    pub fn merge(&mut self, other: ProjectStructure) {
+        let existing: std::collections::HashSet<String> =
+            self.modules.iter().map(|m| m.id.clone()).collect();
+        let mut renamed = std::collections::HashMap::new();
+
+        for mut module in other.modules {
+            if existing.contains(&module.id) {
+                let new_id = format!("{:?}_{}", module.language, module.id).to_lowercase();
+                renamed.insert(module.id.clone(), new_id.clone());
+                module.id = new_id;
+            }
+            self.modules.push(module);
+        }
+
+        for mut rel in other.relationships {
+            if let Some(id) = renamed.get(&rel.from) {
+                rel.from = id.clone();
+            }
+            if let Some(id) = renamed.get(&rel.to) {
+                rel.to = id.clone();
+            }
+            self.relationships.push(rel);
+        }
+
+        self.dependencies.extend(other.dependencies);
+        for skipped in other.diagnostics.skipped {
+            match self.diagnostics.skipped.iter_mut().find(|s| s.path == skipped.path) {
+                Some(existing) => existing.file_count += skipped.file_count,
+                None => self.diagnostics.skipped.push(skipped),
+            }
+        }
+        self.diagnostics.manifest_errors.extend(other.diagnostics.manifest_errors);
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalysisOptions {
+    #[serde(default)]
+    pub include_ignored: bool,
+    // Days of git history counted as churn; churn is skipped when unset
+    #[serde(default)]
+    pub churn_days: Option<u32>,
+    // Run git blame to find each module's primary authors (slow on large
+    // repositories)
+    #[serde(default)]
+    pub blame: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportOptions {
+    #[serde(default)]
+    pub format: ExportFormat,
+    // Only export this module and the modules below it
+    #[serde(default)]
+    pub root: Option<String>,
+    // Levels below `root` (or below the top) to include
+    #[serde(default)]
+    pub depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Dot,
+    Graphml,
+    Gexf,
+    #[serde(rename = "plantuml-component")]
+    PlantumlComponent,
+    #[serde(rename = "plantuml-class")]
+    PlantumlClass,
+    Structurizr,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalysisDiagnostics {
+    pub skipped: Vec<SkippedPath>,
+    // Dependency manifests that could not be read; their dependencies are missing
+    #[serde(default)]
+    pub manifest_errors: Vec<ManifestError>,
+    // CODEOWNERS file that module owners were taken from, relative to the
+    // repository root
+    #[serde(default)]
+    pub codeowners: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestError {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedPath {
+    pub path: String,
+    pub reason: SkipReason,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    Ignored,
+    DefaultExclude,
+    Virtualenv,
+}
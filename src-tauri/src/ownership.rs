@@ -0,0 +1,275 @@
+use crate::export;
+use crate::models::*;
+use git2::Repository;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// Where GitHub and GitLab look for the file, relative to the repository root
+const CODEOWNERS_LOCATIONS: &[&str] = &[
+    ".github/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+];
+
+const PRIMARY_AUTHORS: usize = 3;
+
+// A pattern and the owners it assigns; no owners means the section's
+// default owners (GitLab) or explicitly unowned (GitHub)
+struct Rule {
+    matcher: Gitignore,
+    // `dir/*` owns the files directly in `dir`, not those in subdirectories
+    direct_children_only: bool,
+    owners: Vec<String>,
+}
+
+impl Rule {
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        match self.direct_children_only {
+            true => self.matcher.matched(path, is_dir).is_ignore(),
+            false => self.matcher.matched_path_or_any_parents(path, is_dir).is_ignore(),
+        }
+    }
+}
+
+// GitLab's `[Section] @default-owners`; a GitHub file is a single section
+struct Section {
+    default_owners: Vec<String>,
+    rules: Vec<Rule>,
+}
+
+pub struct CodeOwners {
+    root: PathBuf,
+    sections: Vec<Section>,
+}
+
+impl CodeOwners {
+    // The first CODEOWNERS file of the repository at `root`, and where it is
+    pub fn find(root: &Path) -> Option<(&'static str, Self)> {
+        CODEOWNERS_LOCATIONS.iter().find_map(|location| {
+            let content = fs::read_to_string(root.join(location)).ok()?;
+            Some((*location, Self::parse(root, &content)))
+        })
+    }
+
+    pub fn parse(root: &Path, content: &str) -> Self {
+        static SECTION_HEADER: OnceLock<Regex> = OnceLock::new();
+        let section_header = SECTION_HEADER.get_or_init(|| {
+            Regex::new(r"^\^?\[([^\]]+)\](?:\[\d+\])?\s*(.*)$").expect("valid section header regex")
+        });
+        let mut sections = vec![Section {
+            default_owners: Vec::new(),
+            rules: Vec::new(),
+        }];
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(caps) = section_header.captures(line) {
+                sections.push(Section {
+                    default_owners: split_tokens(&caps[2]),
+                    rules: Vec::new(),
+                });
+                continue;
+            }
+
+            let mut tokens = split_tokens(line);
+            if tokens.is_empty() {
+                continue;
+            }
+            let pattern = tokens.remove(0);
+            // Negation is not part of either syntax
+            if pattern.starts_with('!') {
+                continue;
+            }
+
+            let mut builder = GitignoreBuilder::new(root);
+            let escaped = match pattern.starts_with('#') {
+                true => format!("\\{}", pattern),
+                false => pattern.clone(),
+            };
+            let matcher = builder
+                .add_line(None, &escaped)
+                .map_err(|e| e.to_string())
+                .and_then(|b| b.build().map_err(|e| e.to_string()));
+            match matcher {
+                Ok(matcher) => sections.last_mut().unwrap().rules.push(Rule {
+                    matcher,
+                    direct_children_only: pattern.ends_with("/*"),
+                    owners: tokens,
+                }),
+                Err(e) => eprintln!("Invalid CODEOWNERS pattern {}: {}", pattern, e),
+            }
+        }
+
+        Self {
+            root: root.to_path_buf(),
+            sections,
+        }
+    }
+
+    // Owners of a file or directory under the repository root. The last
+    // matching rule of each section wins, and sections add up.
+    pub fn owners(&self, path: &Path, is_dir: bool) -> Vec<String> {
+        let mut owners: Vec<String> = Vec::new();
+        if !path.starts_with(&self.root) {
+            return owners;
+        }
+        for section in &self.sections {
+            let Some(rule) = section.rules.iter().rev().find(|r| r.matches(path, is_dir)) else {
+                continue;
+            };
+            let found = match rule.owners.is_empty() {
+                true => &section.default_owners,
+                false => &rule.owners,
+            };
+            for owner in found {
+                if !owners.contains(owner) {
+                    owners.push(owner.clone());
+                }
+            }
+        }
+        owners
+    }
+}
+
+// Whitespace-separated tokens, honoring `\ ` and `\#` escapes and stopping at
+// a trailing comment
+fn split_tokens(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => current.extend(chars.next()),
+            '#' if current.is_empty() => break,
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+// Assigns CODEOWNERS owners to modules and, only with `options.blame` (off
+// by default), their primary authors
+pub fn link_owners(structure: &mut ProjectStructure, root_path: &Path, options: &AnalysisOptions) {
+    let Ok(project) = root_path.canonicalize() else {
+        return;
+    };
+    let repo = Repository::discover(&project).ok();
+    // CODEOWNERS paths are relative to the repository root
+    let workdir = repo
+        .as_ref()
+        .and_then(|r| r.workdir())
+        .and_then(|w| w.canonicalize().ok());
+    let root = workdir.clone().unwrap_or_else(|| project.clone());
+
+    if let Some((location, codeowners)) = CodeOwners::find(&root) {
+        structure.diagnostics.codeowners = Some(location.to_string());
+        for module in &mut structure.modules {
+            // A Go package is owned as a directory, like the go tool sees it
+            let path = Path::new(&module.path);
+            let path = match module.files.is_empty() {
+                true => Some(path),
+                false => path.parent(),
+            };
+            if let Some(Ok(path)) = path.map(Path::canonicalize) {
+                module.owners = codeowners.owners(&path, path.is_dir());
+            }
+        }
+    }
+
+    if let (true, Some(repo), Some(workdir)) = (options.blame, &repo, &workdir) {
+        eprintln!("Running git blame on {} modules", structure.modules.len());
+        for module in &mut structure.modules {
+            module.primary_authors = primary_authors(repo, workdir, module);
+        }
+    }
+}
+
+fn primary_authors(repo: &Repository, workdir: &Path, module: &Module) -> Vec<Contributor> {
+    let mut lines: HashMap<String, usize> = HashMap::new();
+    for file in module.source_files() {
+        let Ok(file) = Path::new(file).canonicalize() else {
+            continue;
+        };
+        let Ok(relative) = file.strip_prefix(workdir) else {
+            continue;
+        };
+        // Untracked files have no history to blame
+        let Ok(blame) = repo.blame_file(relative, None) else {
+            continue;
+        };
+        for hunk in blame.iter() {
+            let name = hunk.final_signature().name().unwrap_or_default().to_string();
+            *lines.entry(name).or_default() += hunk.lines_in_hunk();
+        }
+    }
+
+    let mut authors: Vec<Contributor> = lines
+        .into_iter()
+        .map(|(name, lines)| Contributor { name, lines })
+        .collect();
+    authors.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.name.cmp(&b.name)));
+    authors.truncate(PRIMARY_AUTHORS);
+    authors
+}
+
+pub fn report(structure: &ProjectStructure) -> OwnershipReport {
+    let modules: HashMap<&str, &Module> = structure.modules.iter().map(|m| (m.id.as_str(), m)).collect();
+
+    let mut teams: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for module in &structure.modules {
+        for owner in &module.owners {
+            teams.entry(owner).or_default().push(module.name.clone());
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut boundary_edges = Vec::new();
+    for rel in export::internal_relationships(structure) {
+        if rel.rel_type != RelationType::Uses || !seen.insert((&rel.from, &rel.to)) {
+            continue;
+        }
+        let (from, to) = (modules[rel.from.as_str()], modules[rel.to.as_str()]);
+        if from.owners.is_empty() || to.owners.is_empty() || from.owners.iter().any(|o| to.owners.contains(o)) {
+            continue;
+        }
+        boundary_edges.push(BoundaryEdge {
+            from: rel.from.clone(),
+            to: rel.to.clone(),
+            from_owners: from.owners.clone(),
+            to_owners: to.owners.clone(),
+        });
+    }
+
+    OwnershipReport {
+        codeowners: structure.diagnostics.codeowners.clone(),
+        teams: teams
+            .into_iter()
+            .map(|(owner, modules)| TeamModules {
+                owner: owner.to_string(),
+                modules,
+            })
+            .collect(),
+        boundary_edges,
+        unowned_modules: structure
+            .modules
+            .iter()
+            .filter(|m| m.owners.is_empty())
+            .map(|m| m.name.clone())
+            .collect(),
+    }
+}
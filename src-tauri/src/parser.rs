@@ -2,20 +2,38 @@ use crate::models::*;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
-use syn::{visit::Visit, Item as SynItem, UseTree, Visibility as SynVis};
+use syn::{spanned::Spanned, visit::Visit, Attribute, Item as SynItem, LitStr, Meta, UseTree, Visibility as SynVis};
 
 pub struct RustParser {
+    // The file's module and the inline module being visited in it
+    file_module: String,
+    file_path: String,
     current_module: String,
     items: Vec<Item>,
     uses: Vec<String>,
+    impls: Vec<ImplBlock>,
+    // `mod name;` / `mod name { ... }` declarations with their visibility
+    mod_decls: Vec<(String, Visibility)>,
+    reexports: Vec<ReExport>,
+    module_type: ModuleType,
+    // Inline `mod name { ... }` blocks, as modules of their own with the
+    // declarations made inside them
+    nested_modules: Vec<(Module, Vec<(String, Visibility)>)>,
 }
 
 impl RustParser {
     pub fn new() -> Self {
         Self {
+            file_module: String::new(),
+            file_path: String::new(),
             current_module: String::new(),
             items: Vec::new(),
             uses: Vec::new(),
+            impls: Vec::new(),
+            mod_decls: Vec::new(),
+            reexports: Vec::new(),
+            module_type: ModuleType::Module,
+            nested_modules: Vec::new(),
         }
     }
 
@@ -23,26 +41,88 @@ impl RustParser {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read file: {}", path.display()))?;
 
+        self.file_module = module_path.to_string();
+        self.file_path = path.display().to_string();
         self.current_module = module_path.to_string();
         self.items.clear();
         self.uses.clear();
+        self.impls.clear();
+        self.mod_decls.clear();
+        self.reexports.clear();
+        self.module_type = Self::determine_module_type(path);
+        self.nested_modules.clear();
 
         let syntax = syn::parse_file(&content)
             .with_context(|| format!("Failed to parse file: {}", path.display()))?;
 
         self.visit_file(&syntax);
 
-        let module_type = Self::determine_module_type(path);
-        let id = module_path.replace("::", "_");
+        let mut module = self.module(module_path, self.module_type.clone());
+        module.docs = doc_comment(&syntax.attrs);
+        Ok(module)
+    }
 
-        Ok(Module {
-            id,
-            name: module_path.to_string(),
-            path: path.display().to_string(),
+    // The module collected so far, from the items of the file or of the
+    // inline module being visited
+    fn module(&self, name: &str, module_type: ModuleType) -> Module {
+        Module {
+            id: name.replace("::", "_"),
+            name: name.to_string(),
+            path: self.file_path.clone(),
             module_type,
             visibility: Visibility::Public,
             items: self.items.clone(),
-        })
+            entry_points: Vec::new(),
+            language: Language::Rust,
+            invokes: Vec::new(),
+            impls: self.impls.clone(),
+            package: None,
+            docs: None,
+            reexports: self.reexports.clone(),
+            lines_of_code: None,
+            churn: None,
+            owners: Vec::new(),
+            primary_authors: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    // Visits `mod name { ... }` as a module of its own, so its items are
+    // exported under its path and with its visibility. Modules under a
+    // crate root are top-level, like `mod name;` files.
+    fn visit_inline_mod(&mut self, m: &syn::ItemMod, items: &[SynItem]) {
+        let name = m.ident.to_string();
+        let parent = self.current_module.clone();
+        let is_root = matches!(self.module_type, ModuleType::Library | ModuleType::Binary) && parent == self.file_module;
+        let child = match parent.rsplit_once("::") {
+            Some((prefix, _)) if is_root => format!("{}::{}", prefix, name),
+            None if is_root => name,
+            _ => format!("{}::{}", parent, name),
+        };
+
+        let items_before = std::mem::take(&mut self.items);
+        let decls_before = std::mem::take(&mut self.mod_decls);
+        let reexports_before = std::mem::take(&mut self.reexports);
+        self.current_module = child.clone();
+
+        for item in items {
+            self.visit_item(item);
+        }
+
+        let module_type = match self.module_type {
+            ModuleType::Test | ModuleType::Example | ModuleType::Benchmark => self.module_type.clone(),
+            _ => ModuleType::Module,
+        };
+        let mut module = self.module(&child, module_type);
+        module.docs = doc_comment(&m.attrs);
+        let span = span_of(m);
+        module.lines_of_code = Some(span.end_line + 1 - span.start_line);
+        let decls = std::mem::replace(&mut self.mod_decls, decls_before);
+        self.nested_modules.push((module, decls));
+
+        self.items = items_before;
+        self.reexports = reexports_before;
+        self.current_module = parent;
     }
 
     fn determine_module_type(path: &Path) -> ModuleType {
@@ -82,37 +162,317 @@ impl RustParser {
     pub fn get_uses(&self) -> Vec<String> {
         self.uses.clone()
     }
+
+    pub fn get_mod_decls(&self) -> Vec<(String, Visibility)> {
+        self.mod_decls.clone()
+    }
+
+    pub fn get_nested_modules(&self) -> Vec<(Module, Vec<(String, Visibility)>)> {
+        self.nested_modules.clone()
+    }
+
+    // Recognizes PyO3 `#[pymodule]`, `#[pyfunction]` and `#[pyclass]` items and
+    // the Python name they are exported under (`#[pyo3(name = "...")]`)
+    fn pyo3_binding(attrs: &[Attribute], rust_name: &str) -> Option<Binding> {
+        let mut kind = None;
+        let mut exported_name = None;
+
+        for attr in attrs {
+            let Some(segment) = attr.path().segments.last() else {
+                continue;
+            };
+            let attr_kind = match segment.ident.to_string().as_str() {
+                "pymodule" => Some(BindingKind::Pymodule),
+                "pyfunction" => Some(BindingKind::Pyfunction),
+                "pyclass" => Some(BindingKind::Pyclass),
+                "pyo3" => None,
+                _ => continue,
+            };
+            if attr_kind.is_some() {
+                kind = attr_kind;
+            }
+
+            if let Meta::List(_) = attr.meta {
+                let _ = attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: LitStr = meta.value()?.parse()?;
+                        exported_name = Some(value.value());
+                    } else if meta.input.peek(syn::Token![=]) {
+                        let _: syn::Expr = meta.value()?.parse()?;
+                    }
+                    Ok(())
+                });
+            }
+        }
+
+        kind.map(|kind| Binding {
+            kind,
+            exported_name: exported_name.unwrap_or_else(|| rust_name.to_string()),
+            exposes: Vec::new(),
+        })
+    }
+
+    // `#[tauri::command]` handlers, and the function registering them with
+    // `generate_handler!` (usually `main`)
+    fn tauri_binding(func: &syn::ItemFn) -> Option<Binding> {
+        let name = func.sig.ident.to_string();
+        let is_command = func.attrs.iter().any(|attr| {
+            let segments: Vec<String> = attr.path().segments.iter().map(|s| s.ident.to_string()).collect();
+            segments == ["tauri", "command"] || segments == ["command"]
+        });
+        if is_command {
+            return Some(Binding {
+                kind: BindingKind::TauriCommand,
+                exported_name: name,
+                exposes: Vec::new(),
+            });
+        }
+
+        let mut handlers = TauriHandlers::default();
+        handlers.visit_block(&func.block);
+        (!handlers.commands.is_empty()).then_some(Binding {
+            kind: BindingKind::TauriHandler,
+            exported_name: name,
+            exposes: handlers.commands,
+        })
+    }
+}
+
+// Collects the command names passed to `tauri::generate_handler![...]`
+#[derive(Default)]
+struct TauriHandlers {
+    commands: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for TauriHandlers {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac.path.segments.last().is_some_and(|s| s.ident == "generate_handler") {
+            for path in mac.tokens.to_string().split(',') {
+                let name = path.rsplit("::").next().unwrap_or(path).trim();
+                if !name.is_empty() && !self.commands.iter().any(|c| c == name) {
+                    self.commands.push(name.to_string());
+                }
+            }
+        }
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+// Collects what a `#[pymodule]` adds to the Python module: `wrap_pyfunction!(f)`,
+// `add_class::<T>()`, `wrap_pymodule!(m)` and `#[pymodule_export] use ...`
+#[derive(Default)]
+struct PyModuleExports {
+    names: Vec<String>,
+}
+
+impl PyModuleExports {
+    fn push(&mut self, path: &str) {
+        let name = path.rsplit("::").next().unwrap_or(path).trim().to_string();
+        if !name.is_empty() && !self.names.contains(&name) {
+            self.names.push(name);
+        }
+    }
+
+    fn push_use_tree(&mut self, tree: &UseTree) {
+        match tree {
+            UseTree::Path(p) => self.push_use_tree(&p.tree),
+            UseTree::Name(n) => self.push(&n.ident.to_string()),
+            UseTree::Rename(r) => self.push(&r.ident.to_string()),
+            UseTree::Group(g) => g.items.iter().for_each(|t| self.push_use_tree(t)),
+            UseTree::Glob(_) => {}
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for PyModuleExports {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let Some(segment) = mac.path.segments.last() else {
+            return;
+        };
+        if segment.ident == "wrap_pyfunction" || segment.ident == "wrap_pymodule" {
+            let tokens = mac.tokens.to_string();
+            let target = tokens.split(',').next().unwrap_or("").replace(' ', "");
+            self.push(&target);
+        }
+        syn::visit::visit_macro(self, mac);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "add_class" {
+            if let Some(turbofish) = &call.turbofish {
+                for arg in &turbofish.args {
+                    if let syn::GenericArgument::Type(syn::Type::Path(ty)) = arg {
+                        if let Some(segment) = ty.path.segments.last() {
+                            self.push(&segment.ident.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_item_use(&mut self, use_item: &'ast syn::ItemUse) {
+        if use_item.attrs.iter().any(|a| a.path().is_ident("pymodule_export")) {
+            self.push_use_tree(&use_item.tree);
+        }
+    }
+
+    fn visit_item_fn(&mut self, func: &'ast syn::ItemFn) {
+        // Functions declared inside a declarative #[pymodule] mod are exported
+        if func.attrs.iter().any(|a| a.path().segments.last().is_some_and(|s| s.ident == "pyfunction")) {
+            self.push(&func.sig.ident.to_string());
+        }
+        syn::visit::visit_item_fn(self, func);
+    }
+
+    fn visit_item_struct(&mut self, s: &'ast syn::ItemStruct) {
+        if s.attrs.iter().any(|a| a.path().segments.last().is_some_and(|seg| seg.ident == "pyclass")) {
+            self.push(&s.ident.to_string());
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for RustParser {
     fn visit_item(&mut self, item: &'ast SynItem) {
+        let span = Some(span_of(item));
+        let docs = doc_comment(item_attrs(item));
         match item {
             SynItem::Fn(func) => {
+                let name = func.sig.ident.to_string();
+                let binding = Self::pyo3_binding(&func.attrs, &name).map(|mut binding| {
+                    if binding.kind == BindingKind::Pymodule {
+                        let mut exports = PyModuleExports::default();
+                        exports.visit_block(&func.block);
+                        binding.exposes = exports.names;
+                    }
+                    binding
+                })
+                .or_else(|| Self::tauri_binding(func));
                 self.items.push(Item {
-                    name: func.sig.ident.to_string(),
+                    name,
                     item_type: ItemType::Function,
                     visibility: Self::convert_visibility(&func.vis),
+                    bases: Vec::new(),
+                    binding,
+                    span,
+                    docs,
+                    signature: Some(fn_signature(&func.vis, &func.sig)),
+                    fields: Vec::new(),
+                    effective_visibility: None,
+                    non_exhaustive: false,
                 });
             }
             SynItem::Struct(s) => {
+                let name = s.ident.to_string();
                 self.items.push(Item {
-                    name: s.ident.to_string(),
+                    binding: Self::pyo3_binding(&s.attrs, &name),
+                    name,
                     item_type: ItemType::Struct,
                     visibility: Self::convert_visibility(&s.vis),
+                    bases: Vec::new(),
+                    span,
+                    docs,
+                    signature: None,
+                    fields: s.fields.iter().map(field_text).collect(),
+                    effective_visibility: None,
+                    non_exhaustive: is_non_exhaustive(&s.attrs),
                 });
             }
             SynItem::Enum(e) => {
+                let name = e.ident.to_string();
                 self.items.push(Item {
-                    name: e.ident.to_string(),
+                    binding: Self::pyo3_binding(&e.attrs, &name),
+                    name,
                     item_type: ItemType::Enum,
                     visibility: Self::convert_visibility(&e.vis),
+                    bases: Vec::new(),
+                    span,
+                    docs,
+                    signature: None,
+                    fields: e.variants.iter().map(variant_text).collect(),
+                    effective_visibility: None,
+                    non_exhaustive: is_non_exhaustive(&e.attrs),
                 });
             }
+            SynItem::Mod(m) => {
+                let name = m.ident.to_string();
+                if !is_cfg_test(&m.attrs) {
+                    self.mod_decls.push((name.clone(), Self::convert_visibility(&m.vis)));
+                }
+
+                // Declarative `#[pymodule] mod name { ... }` modules
+                if let Some(mut binding) = Self::pyo3_binding(&m.attrs, &name) {
+                    let mut exports = PyModuleExports::default();
+                    exports.visit_item_mod(m);
+                    binding.exposes = exports.names;
+                    self.items.push(Item {
+                        name,
+                        item_type: ItemType::Module,
+                        visibility: Self::convert_visibility(&m.vis),
+                        bases: Vec::new(),
+                        binding: Some(binding),
+                        span,
+                        docs,
+                        signature: None,
+                        fields: Vec::new(),
+                        effective_visibility: None,
+                        non_exhaustive: false,
+                    });
+                }
+            }
+            SynItem::Impl(imp) => {
+                if let Some(self_type) = type_name(&imp.self_ty) {
+                    let trait_name = imp
+                        .trait_
+                        .as_ref()
+                        .and_then(|(_, path, _)| path.segments.last())
+                        .map(|s| s.ident.to_string());
+                    let methods = imp
+                        .items
+                        .iter()
+                        .filter_map(|item| match item {
+                            syn::ImplItem::Fn(method) => Some(Item {
+                                name: method.sig.ident.to_string(),
+                                item_type: ItemType::Function,
+                                // Trait methods are as visible as the trait
+                                visibility: if trait_name.is_some() {
+                                    Visibility::Public
+                                } else {
+                                    Self::convert_visibility(&method.vis)
+                                },
+                                bases: Vec::new(),
+                                binding: None,
+                                span: Some(span_of(method)),
+                                docs: doc_comment(&method.attrs),
+                                signature: Some(fn_signature(&method.vis, &method.sig)),
+                                fields: Vec::new(),
+                                effective_visibility: None,
+                                non_exhaustive: false,
+                            }),
+                            _ => None,
+                        })
+                        .collect();
+                    self.impls.push(ImplBlock {
+                        self_type,
+                        trait_name,
+                        methods,
+                    });
+                }
+            }
             SynItem::Trait(t) => {
                 self.items.push(Item {
                     name: t.ident.to_string(),
                     item_type: ItemType::Trait,
                     visibility: Self::convert_visibility(&t.vis),
+                    bases: Vec::new(),
+                    binding: None,
+                    span,
+                    docs,
+                    signature: Some(trait_header(t)),
+                    fields: t.items.iter().filter_map(trait_member_text).collect(),
+                    effective_visibility: None,
+                    non_exhaustive: false,
                 });
             }
             SynItem::Const(c) => {
@@ -120,6 +480,14 @@ impl<'ast> Visit<'ast> for RustParser {
                     name: c.ident.to_string(),
                     item_type: ItemType::Const,
                     visibility: Self::convert_visibility(&c.vis),
+                    bases: Vec::new(),
+                    binding: None,
+                    span,
+                    docs,
+                    signature: Some(const_declaration(c)),
+                    fields: Vec::new(),
+                    effective_visibility: None,
+                    non_exhaustive: false,
                 });
             }
             SynItem::Static(s) => {
@@ -127,6 +495,14 @@ impl<'ast> Visit<'ast> for RustParser {
                     name: s.ident.to_string(),
                     item_type: ItemType::Static,
                     visibility: Self::convert_visibility(&s.vis),
+                    bases: Vec::new(),
+                    binding: None,
+                    span,
+                    docs,
+                    signature: Some(static_declaration(s)),
+                    fields: Vec::new(),
+                    effective_visibility: None,
+                    non_exhaustive: false,
                 });
             }
             SynItem::Type(t) => {
@@ -134,6 +510,14 @@ impl<'ast> Visit<'ast> for RustParser {
                     name: t.ident.to_string(),
                     item_type: ItemType::Type,
                     visibility: Self::convert_visibility(&t.vis),
+                    bases: Vec::new(),
+                    binding: None,
+                    span,
+                    docs,
+                    signature: Some(type_declaration(t)),
+                    fields: Vec::new(),
+                    effective_visibility: None,
+                    non_exhaustive: false,
                 });
             }
             SynItem::Macro(m) => {
@@ -142,20 +526,255 @@ impl<'ast> Visit<'ast> for RustParser {
                         name: ident.to_string(),
                         item_type: ItemType::Macro,
                         visibility: Visibility::Public,
+                        bases: Vec::new(),
+                        binding: None,
+                        span,
+                        docs,
+                        signature: None,
+                        fields: Vec::new(),
+                        effective_visibility: None,
+                        non_exhaustive: false,
                     });
                 }
             }
             _ => {}
         }
-        syn::visit::visit_item(self, item);
+
+        // Test modules aren't part of the crate that is built
+        match item {
+            SynItem::Mod(m) => {
+                if let Some((_, items)) = m.content.as_ref().filter(|_| !is_cfg_test(&m.attrs)) {
+                    self.visit_inline_mod(m, items);
+                }
+            }
+            _ => syn::visit::visit_item(self, item),
+        }
     }
 
     fn visit_item_use(&mut self, use_item: &'ast syn::ItemUse) {
         self.extract_use_paths(&use_item.tree);
+        if !matches!(use_item.vis, SynVis::Inherited) {
+            let visibility = Self::convert_visibility(&use_item.vis);
+            flatten_use_tree(&use_item.tree, &mut Vec::new(), &mut |path, name| {
+                self.reexports.push(ReExport {
+                    path,
+                    name,
+                    visibility: visibility.clone(),
+                    target: None,
+                });
+            });
+        }
         syn::visit::visit_item_use(self, use_item);
     }
 }
 
+// Source text of a single item as formatted by prettyplease
+fn unparse(item: SynItem) -> String {
+    let file = syn::File {
+        shebang: None,
+        attrs: Vec::new(),
+        items: vec![item],
+    };
+    prettyplease::unparse(&file).trim().to_string()
+}
+
+// `pub fn name<T>(arg: T) -> Out`, without attributes or body
+fn fn_signature(vis: &SynVis, sig: &syn::Signature) -> String {
+    let func: syn::ItemFn = syn::parse_quote!(#vis #sig {});
+    let text = unparse(SynItem::Fn(func));
+    text.strip_suffix("{}").unwrap_or(&text).trim_end().to_string()
+}
+
+// `pub name: Type` (or just `Type` for tuple fields), without attributes
+fn field_text(field: &syn::Field) -> String {
+    let mut field = field.clone();
+    field.attrs.clear();
+    let (text, prefix, suffix) = match field.ident {
+        Some(_) => (unparse(syn::parse_quote!(struct S { #field })), "struct S {", "}"),
+        None => (unparse(syn::parse_quote!(struct S(#field);)), "struct S(", ");"),
+    };
+    one_line(text.strip_prefix(prefix).and_then(|t| t.strip_suffix(suffix)).unwrap_or(&text))
+}
+
+// `Name`, `Name(T)`, `Name { field: T }` or `Name = 1`, without attributes
+fn variant_text(variant: &syn::Variant) -> String {
+    let mut variant = variant.clone();
+    variant.attrs.clear();
+    let text = unparse(syn::parse_quote!(enum E { #variant }));
+    one_line(text.strip_prefix("enum E {").and_then(|t| t.strip_suffix('}')).unwrap_or(&text))
+}
+
+// `pub trait Name<T>: Super`, without attributes or members
+fn trait_header(t: &syn::ItemTrait) -> String {
+    let mut t = t.clone();
+    t.attrs.clear();
+    t.items.clear();
+    let text = unparse(SynItem::Trait(t));
+    text.strip_suffix("{}").unwrap_or(&text).trim_end().to_string()
+}
+
+// `fn name(&self) -> T;`, `type Name: Bound;` or `const NAME: T;`, with
+// members that have a default ending in `{ .. }` or `= ..;` instead of `;`
+fn trait_member_text(member: &syn::TraitItem) -> Option<String> {
+    let (member, has_default) = match member {
+        syn::TraitItem::Fn(f) => {
+            let text = fn_signature(&SynVis::Inherited, &f.sig);
+            return Some(format!("{}{}", text, if f.default.is_some() { " { .. }" } else { ";" }));
+        }
+        syn::TraitItem::Const(c) => {
+            let bare = syn::TraitItemConst { attrs: Vec::new(), default: None, ..c.clone() };
+            (syn::TraitItem::Const(bare), c.default.is_some())
+        }
+        syn::TraitItem::Type(t) => {
+            let bare = syn::TraitItemType { attrs: Vec::new(), default: None, ..t.clone() };
+            (syn::TraitItem::Type(bare), t.default.is_some())
+        }
+        _ => return None,
+    };
+    let text = one_line(&unparse(syn::parse_quote!(trait T { #member })));
+    let text = text.strip_prefix("trait T {").and_then(|t| t.strip_suffix('}')).unwrap_or(&text).trim();
+    Some(match has_default {
+        true => format!("{} = ..;", text.trim_end_matches(';')),
+        false => text.to_string(),
+    })
+}
+
+// `pub const NAME: Type`, without the value
+fn const_declaration(c: &syn::ItemConst) -> String {
+    let (vis, ident, generics, ty) = (&c.vis, &c.ident, &c.generics, &c.ty);
+    let text = unparse(syn::parse_quote!(#vis const #ident #generics: #ty = _;));
+    text.strip_suffix(" = _;").unwrap_or(&text).to_string()
+}
+
+// `pub static mut NAME: Type`, without the value
+fn static_declaration(s: &syn::ItemStatic) -> String {
+    let (vis, mutability, ident, ty) = (&s.vis, &s.mutability, &s.ident, &s.ty);
+    let text = unparse(syn::parse_quote!(#vis static #mutability #ident: #ty = _;));
+    text.strip_suffix(" = _;").unwrap_or(&text).to_string()
+}
+
+// `pub type Name<T> = Target`
+fn type_declaration(t: &syn::ItemType) -> String {
+    let mut t = t.clone();
+    t.attrs.clear();
+    let text = unparse(SynItem::Type(t));
+    one_line(text.strip_suffix(';').unwrap_or(&text))
+}
+
+fn one_line(text: &str) -> String {
+    let text = text.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+    text.replace(", }", " }").trim().trim_end_matches(',').to_string()
+}
+
+fn span_of<T: Spanned>(node: &T) -> Span {
+    let span = node.span();
+    Span {
+        start_line: span.start().line,
+        end_line: span.end().line,
+    }
+}
+
+// `#[cfg(test)]`, alone or in an `all(...)`
+fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().filter(|a| a.path().is_ident("cfg")).any(|a| {
+        let Ok(list) = a.meta.require_list() else {
+            return false;
+        };
+        let tokens = list.tokens.to_string();
+        let has_test = tokens.split(|c: char| !c.is_alphanumeric() && c != '_').any(|t| t == "test");
+        tokens == "test" || (tokens.starts_with("all") && has_test)
+    })
+}
+
+fn is_non_exhaustive(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|a| a.path().is_ident("non_exhaustive"))
+}
+
+// `///` and `/** */` comments are `#[doc = "..."]` attributes to syn
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let text = lines
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn item_attrs(item: &SynItem) -> &[Attribute] {
+    match item {
+        SynItem::Fn(i) => &i.attrs,
+        SynItem::Struct(i) => &i.attrs,
+        SynItem::Enum(i) => &i.attrs,
+        SynItem::Mod(i) => &i.attrs,
+        SynItem::Trait(i) => &i.attrs,
+        SynItem::Const(i) => &i.attrs,
+        SynItem::Static(i) => &i.attrs,
+        SynItem::Type(i) => &i.attrs,
+        SynItem::Macro(i) => &i.attrs,
+        _ => &[],
+    }
+}
+
+// Calls `emit(path, name)` for every import of a use tree:
+// `a::{b::C, D as E, f::*}` -> (a::b::C, C), (a::D, E), (a::f, *)
+fn flatten_use_tree(tree: &UseTree, prefix: &mut Vec<String>, emit: &mut impl FnMut(String, String)) {
+    match tree {
+        UseTree::Path(p) => {
+            prefix.push(p.ident.to_string());
+            flatten_use_tree(&p.tree, prefix, emit);
+            prefix.pop();
+        }
+        UseTree::Name(n) => {
+            // `use a::b::{self}` imports `a::b` itself
+            let name = if n.ident == "self" {
+                prefix.last().cloned().unwrap_or_default()
+            } else {
+                n.ident.to_string()
+            };
+            let mut path = prefix.clone();
+            if n.ident != "self" {
+                path.push(name.clone());
+            }
+            emit(path.join("::"), name);
+        }
+        UseTree::Rename(r) => {
+            let mut path = prefix.clone();
+            if r.ident != "self" {
+                path.push(r.ident.to_string());
+            }
+            emit(path.join("::"), r.rename.to_string());
+        }
+        UseTree::Glob(_) => emit(prefix.join("::"), "*".to_string()),
+        UseTree::Group(g) => {
+            for item in &g.items {
+                flatten_use_tree(item, prefix, emit);
+            }
+        }
+    }
+}
+
+// `Foo`, `foo::Foo<T>`, `&Foo` -> `Foo`
+fn type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        syn::Type::Reference(r) => type_name(&r.elem),
+        syn::Type::Paren(p) => type_name(&p.elem),
+        _ => None,
+    }
+}
+
 impl RustParser {
     fn extract_use_paths(&mut self, tree: &UseTree) {
         match tree {
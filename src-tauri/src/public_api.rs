@@ -0,0 +1,392 @@
+use crate::models::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+// Guards against re-export cycles and absurdly deep chains
+const MAX_RESOLVE_DEPTH: usize = 16;
+
+// What a path in a `use` or a module resolves to
+enum Target<'a> {
+    // Crate-relative module path, "" for the crate root
+    Module(String),
+    Item(&'a Module, &'a Item),
+}
+
+// The modules of one crate, keyed by crate-relative path
+struct Crate<'a> {
+    name: String,
+    modules: HashMap<String, &'a Module>,
+    // Module id -> crate-relative path
+    paths: HashMap<&'a str, String>,
+}
+
+impl<'a> Crate<'a> {
+    fn new(structure: &'a ProjectStructure, root: &'a Module) -> Self {
+        // Workspace member modules are named `member::lib`, `member::foo`, ...
+        // (ids `member_lib`, `member_foo`), so the root's name gives the prefix
+        let prefix = root
+            .name
+            .strip_suffix("lib")
+            .or_else(|| root.name.strip_suffix("main"))
+            .unwrap_or("");
+        let mut modules = HashMap::new();
+        modules.insert(String::new(), root);
+        for module in structure.modules.iter().filter(|m| {
+            m.language == Language::Rust && m.module_type == ModuleType::Module && m.package == root.package
+        }) {
+            if let Some(path) = module.name.strip_prefix(prefix) {
+                modules.insert(path.to_string(), module);
+            }
+        }
+
+        Self {
+            name: root
+                .package
+                .as_deref()
+                .map_or_else(|| "crate".to_string(), |p| p.replace('-', "_")),
+            paths: modules.iter().map(|(path, m)| (m.id.as_str(), path.clone())).collect(),
+            modules,
+        }
+    }
+
+    // Every crate with a library root, and binary crates without one
+    fn all(structure: &'a ProjectStructure) -> Vec<Self> {
+        let roots = structure.modules.iter().filter(|m| m.language == Language::Rust);
+        let libraries: Vec<&Module> = roots.clone().filter(|m| m.module_type == ModuleType::Library).collect();
+        let binaries = roots.filter(|m| {
+            m.module_type == ModuleType::Binary && !libraries.iter().any(|lib| lib.package == m.package)
+        });
+        libraries
+            .iter()
+            .copied()
+            .chain(binaries)
+            .map(|root| Self::new(structure, root))
+            .collect()
+    }
+
+    // Full path of a module given its crate-relative path
+    fn module_path(&self, path: &str) -> String {
+        join(&self.name, path)
+    }
+
+    fn item_path(&self, module: &Module, name: &str) -> String {
+        let path = self.paths.get(module.id.as_str()).map_or("", |p| p.as_str());
+        join(&self.module_path(path), name)
+    }
+
+    fn target_path(&self, target: &Target) -> String {
+        match target {
+            Target::Module(path) => self.module_path(path),
+            Target::Item(module, item) => self.item_path(module, &item.name),
+        }
+    }
+
+    // Resolves `name` as seen from inside module `scope`: a child module, an
+    // item defined there, or something the module imports with `pub use`
+    fn resolve_name(&self, scope: &str, name: &str, depth: usize) -> Option<Target<'a>> {
+        if depth > MAX_RESOLVE_DEPTH {
+            return None;
+        }
+        let child = join(scope, name);
+        if self.modules.contains_key(&child) {
+            return Some(Target::Module(child));
+        }
+
+        let module = self.modules.get(scope)?;
+        if let Some(item) = module.items.iter().find(|i| i.name == name) {
+            return Some(Target::Item(module, item));
+        }
+        if let Some(reexport) = module.reexports.iter().find(|r| r.name == name) {
+            return self.resolve_path(scope, &reexport.path, depth + 1);
+        }
+        module
+            .reexports
+            .iter()
+            .filter(|r| r.name == "*")
+            .find_map(|r| match self.resolve_path(scope, &r.path, depth + 1)? {
+                Target::Module(glob) => self.resolve_name(&glob, name, depth + 1),
+                Target::Item(..) => None,
+            })
+    }
+
+    // Resolves a `use` path written in module `scope`; paths into other
+    // crates resolve to nothing
+    fn resolve_path(&self, scope: &str, path: &str, depth: usize) -> Option<Target<'a>> {
+        let segments: Vec<&str> = path.split("::").collect();
+        let mut current = scope.to_string();
+        for (index, segment) in segments.iter().enumerate() {
+            match *segment {
+                "crate" if index == 0 => current.clear(),
+                "self" => {}
+                "super" => current = parent(&current)?.to_string(),
+                _ if index == 0 && *segment == self.name => current.clear(),
+                _ => match self.resolve_name(&current, segment, depth)? {
+                    target if index == segments.len() - 1 => return Some(target),
+                    Target::Module(module) => current = module,
+                    Target::Item(..) => return None,
+                },
+            }
+        }
+        Some(Target::Module(current))
+    }
+}
+
+// Walks a crate from its root, collecting every path that reaches an item
+struct Exporter<'a, 'c> {
+    krate: &'c Crate<'a>,
+    api: Vec<PublicPath>,
+    // (module id, item name); methods are named `Type::method`
+    reachable: HashSet<(String, String)>,
+    visited: HashSet<(String, String)>,
+}
+
+impl<'a, 'c> Exporter<'a, 'c> {
+    fn export_module(&mut self, scope: &str, prefix: &str, depth: usize) {
+        if depth > MAX_RESOLVE_DEPTH || !self.visited.insert((scope.to_string(), prefix.to_string())) {
+            return;
+        }
+        let Some(module) = self.krate.modules.get(scope).copied() else {
+            return;
+        };
+
+        for item in module.items.iter().filter(|i| i.visibility == Visibility::Public) {
+            self.push_item(prefix, &item.name, module, item);
+        }
+
+        let mut children: Vec<(&String, &Module)> = self
+            .krate
+            .modules
+            .iter()
+            .filter(|(path, m)| parent(path) == Some(scope) && m.visibility == Visibility::Public)
+            .map(|(path, m)| (path, *m))
+            .collect();
+        children.sort_by_key(|(path, _)| *path);
+        for (path, child) in children {
+            let name = path.rsplit("::").next().unwrap_or(path);
+            self.push_module(prefix, name, path, child, depth);
+        }
+
+        for reexport in module.reexports.iter().filter(|r| r.visibility == Visibility::Public) {
+            match self.krate.resolve_path(scope, &reexport.path, 0) {
+                Some(Target::Module(path)) if reexport.name == "*" => self.export_module(&path, prefix, depth + 1),
+                Some(Target::Module(path)) => {
+                    let target = self.krate.modules[&path];
+                    self.push_module(prefix, &reexport.name, &path, target, depth);
+                }
+                Some(Target::Item(defined_in, item)) if reexport.name != "*" => {
+                    self.push_item(prefix, &reexport.name, defined_in, item)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn push_module(&mut self, prefix: &str, name: &str, path: &str, module: &Module, depth: usize) {
+        let public_path = format!("{}::{}", prefix, name);
+        self.api.push(PublicPath {
+            path: public_path.clone(),
+            item_type: ItemType::Module,
+            module_id: module.id.clone(),
+            name: module.name.clone(),
+            definition: self.krate.module_path(path),
+        });
+        self.export_module(path, &public_path, depth + 1);
+    }
+
+    fn push_item(&mut self, prefix: &str, name: &str, module: &Module, item: &Item) {
+        let path = format!("{}::{}", prefix, name);
+        let definition = self.krate.item_path(module, &item.name);
+        self.api.push(PublicPath {
+            path: path.clone(),
+            item_type: item.item_type.clone(),
+            module_id: module.id.clone(),
+            name: item.name.clone(),
+            definition: definition.clone(),
+        });
+        self.reachable.insert((module.id.clone(), item.name.clone()));
+
+        if !matches!(item.item_type, ItemType::Struct | ItemType::Enum | ItemType::Type) {
+            return;
+        }
+        // Inherent methods may be implemented in any module of the crate
+        for impl_module in self.krate.modules.values() {
+            for block in impl_module
+                .impls
+                .iter()
+                .filter(|b| b.self_type == item.name && b.trait_name.is_none())
+            {
+                for method in block.methods.iter().filter(|m| m.visibility == Visibility::Public) {
+                    let name = format!("{}::{}", item.name, method.name);
+                    self.api.push(PublicPath {
+                        path: format!("{}::{}", path, method.name),
+                        item_type: ItemType::Function,
+                        module_id: impl_module.id.clone(),
+                        name: name.clone(),
+                        definition: format!("{}::{}", definition, method.name),
+                    });
+                    self.reachable.insert((impl_module.id.clone(), name));
+                }
+            }
+        }
+    }
+}
+
+// Computes each Rust library's public API: `pub` items of modules reachable
+// from the crate root through `pub mod`s, whatever `pub use` re-exports
+// (globs included) and the inherent `pub` methods of exposed types. Items
+// that are `pub` but unreachable are marked effectively crate-visible.
+pub fn link_public_api(structure: &mut ProjectStructure, _root_path: &Path) {
+    let mut api = Vec::new();
+    let mut reachable = HashSet::new();
+    for root in structure
+        .modules
+        .iter()
+        .filter(|m| m.language == Language::Rust && m.module_type == ModuleType::Library)
+    {
+        let krate = Crate::new(structure, root);
+        let mut exporter = Exporter {
+            krate: &krate,
+            api: Vec::new(),
+            reachable: HashSet::new(),
+            visited: HashSet::new(),
+        };
+        let name = krate.name.clone();
+        exporter.export_module("", &name, 0);
+        api.extend(exporter.api);
+        reachable.extend(exporter.reachable);
+    }
+
+    api.sort_by(|a, b| a.path.cmp(&b.path));
+    api.dedup_by(|a, b| a.path == b.path);
+    let exposed_types: HashSet<&str> = api
+        .iter()
+        .filter(|p| matches!(p.item_type, ItemType::Struct | ItemType::Enum | ItemType::Type))
+        .map(|p| p.name.as_str())
+        .collect();
+
+    let effective = |module: &Module, name: &str, declared: &Visibility| {
+        if reachable.contains(&(module.id.clone(), name.to_string())) {
+            Visibility::Public
+        } else if *declared == Visibility::Public {
+            Visibility::Crate
+        } else {
+            declared.clone()
+        }
+    };
+
+    let mut resolved = Vec::new();
+    for (index, module) in structure.modules.iter().enumerate() {
+        if module.language != Language::Rust
+            || !matches!(module.module_type, ModuleType::Library | ModuleType::Binary | ModuleType::Module)
+        {
+            continue;
+        }
+        let items: Vec<Visibility> = module
+            .items
+            .iter()
+            .map(|item| effective(module, &item.name, &item.visibility))
+            .collect();
+        let methods: Vec<Vec<Visibility>> = module
+            .impls
+            .iter()
+            .map(|block| {
+                block
+                    .methods
+                    .iter()
+                    .map(|method| match &block.trait_name {
+                        // Trait impls are public wherever the type is
+                        Some(_) if exposed_types.contains(block.self_type.as_str()) => Visibility::Public,
+                        Some(_) => Visibility::Crate,
+                        None => effective(
+                            module,
+                            &format!("{}::{}", block.self_type, method.name),
+                            &method.visibility,
+                        ),
+                    })
+                    .collect()
+            })
+            .collect();
+        resolved.push((index, items, methods));
+    }
+
+    for (index, items, methods) in resolved {
+        let module = &mut structure.modules[index];
+        for (item, visibility) in module.items.iter_mut().zip(items) {
+            item.effective_visibility = Some(visibility);
+        }
+        for (block, visibilities) in module.impls.iter_mut().zip(methods) {
+            for (method, visibility) in block.methods.iter_mut().zip(visibilities) {
+                method.effective_visibility = Some(visibility);
+            }
+        }
+    }
+    structure.public_api = api;
+}
+
+// Resolves every Rust `use` re-export to the item or module it names and adds
+// a ReExports edge to the module defining it, so a facade's `pub use
+// inner::deep::Thing` shows where `Thing` really lives
+pub fn link_reexports(structure: &mut ProjectStructure, _root_path: &Path) {
+    let mut targets = Vec::new();
+    // (from, to) -> labels
+    let mut edges: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for krate in Crate::all(structure) {
+        for (scope, module) in &krate.modules {
+            for (index, reexport) in module.reexports.iter().enumerate() {
+                let Some(target) = krate.resolve_path(scope, &reexport.path, 0) else {
+                    continue;
+                };
+                let (to, original) = match &target {
+                    Target::Module(path) => {
+                        let module = krate.modules[path];
+                        (module.id.clone(), path.rsplit("::").next().unwrap_or(path).to_string())
+                    }
+                    Target::Item(module, item) => (module.id.clone(), item.name.clone()),
+                };
+                targets.push((module.id.clone(), index, krate.target_path(&target)));
+
+                if to == module.id {
+                    continue;
+                }
+                let label = if reexport.name == "*" || reexport.name == original {
+                    reexport.name.clone()
+                } else {
+                    format!("{} as {}", original, reexport.name)
+                };
+                let labels = edges.entry((module.id.clone(), to)).or_default();
+                if !labels.contains(&label) {
+                    labels.push(label);
+                }
+            }
+        }
+    }
+
+    for (module_id, index, target) in targets {
+        if let Some(module) = structure.modules.iter_mut().find(|m| m.id == module_id) {
+            module.reexports[index].target = Some(target);
+        }
+    }
+    for ((from, to), labels) in edges {
+        structure.relationships.push(Relationship {
+            from,
+            to,
+            rel_type: RelationType::ReExports,
+            label: Some(labels.join(", ")),
+        });
+    }
+}
+
+fn join(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}::{}", scope, name)
+    }
+}
+
+fn parent(path: &str) -> Option<&str> {
+    if path.is_empty() {
+        return None;
+    }
+    Some(path.rsplit_once("::").map_or("", |(parent, _)| parent))
+}
@@ -0,0 +1,130 @@
+use crate::models::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+struct ExtensionModule {
+    python_names: Vec<String>,
+    rust_module: String,
+    exposes: Vec<String>,
+}
+
+// Links Python imports of a PyO3 extension module to the Rust items the
+// `#[pymodule]` exposes, so mixed maturin projects show one connected graph
+pub fn link_bindings(structure: &mut ProjectStructure, root_path: &Path) {
+    let maturin_name = maturin_module_name(root_path);
+    let extensions = extension_modules(structure, maturin_name.as_deref());
+    if extensions.is_empty() {
+        return;
+    }
+
+    let python_ids: Vec<String> = structure
+        .modules
+        .iter()
+        .filter(|m| m.language == Language::Python)
+        .map(|m| m.id.clone())
+        .collect();
+
+    let mut new_edges = Vec::new();
+    for rel in &structure.relationships {
+        if rel.rel_type != RelationType::Uses || !python_ids.contains(&rel.from) {
+            continue;
+        }
+        for extension in &extensions {
+            if !imports_extension(&rel.to, &extension.python_names) {
+                continue;
+            }
+
+            // Rust module id -> exposed item names defined there
+            let mut targets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for name in &extension.exposes {
+                if let Some(module_id) = find_bound_item(structure, name) {
+                    targets.entry(module_id).or_default().push(name.clone());
+                }
+            }
+            if targets.is_empty() {
+                let name = extension.python_names.last().cloned().unwrap_or_default();
+                targets.insert(extension.rust_module.clone(), vec![name]);
+            }
+
+            for (module_id, names) in targets {
+                new_edges.push(Relationship {
+                    from: rel.from.clone(),
+                    to: module_id,
+                    rel_type: RelationType::Binds,
+                    label: Some(names.join(", ")),
+                });
+            }
+        }
+    }
+
+    for edge in new_edges {
+        let exists = structure
+            .relationships
+            .iter()
+            .any(|r| r.from == edge.from && r.to == edge.to && r.rel_type == edge.rel_type);
+        if !exists {
+            structure.relationships.push(edge);
+        }
+    }
+}
+
+fn extension_modules(structure: &ProjectStructure, maturin_name: Option<&str>) -> Vec<ExtensionModule> {
+    let mut extensions = Vec::new();
+
+    for module in structure.modules.iter().filter(|m| m.language == Language::Rust) {
+        for item in &module.items {
+            let Some(binding) = &item.binding else {
+                continue;
+            };
+            if binding.kind != BindingKind::Pymodule {
+                continue;
+            }
+
+            let mut python_names = vec![binding.exported_name.clone()];
+            if let Some(full_name) = maturin_name {
+                if full_name.rsplit('.').next() == Some(binding.exported_name.as_str()) {
+                    python_names.insert(0, full_name.to_string());
+                }
+            }
+
+            extensions.push(ExtensionModule {
+                python_names,
+                rust_module: module.id.clone(),
+                exposes: binding.exposes.clone(),
+            });
+        }
+    }
+
+    extensions
+}
+
+// Python `Uses` targets are dotted paths with `.` replaced by `_`, so
+// `mypkg._native` arrives as `mypkg__native`
+fn imports_extension(target: &str, python_names: &[String]) -> bool {
+    python_names.iter().any(|name| {
+        let id = name.replace('.', "_");
+        target == id || (!name.contains('.') && target.ends_with(&format!("_{}", id)))
+    })
+}
+
+fn find_bound_item(structure: &ProjectStructure, name: &str) -> Option<String> {
+    let rust_modules = || structure.modules.iter().filter(|m| m.language == Language::Rust);
+
+    rust_modules()
+        .find(|m| m.items.iter().any(|i| i.name == name && i.binding.is_some()))
+        .or_else(|| rust_modules().find(|m| m.items.iter().any(|i| i.name == name)))
+        .map(|m| m.id.clone())
+}
+
+// `[tool.maturin] module-name = "mypkg._native"` gives the full import path
+fn maturin_module_name(root_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(root_path.join("pyproject.toml")).ok()?;
+    let pyproject = content.parse::<toml::Table>().ok()?;
+    pyproject
+        .get("tool")?
+        .get("maturin")?
+        .get("module-name")?
+        .as_str()
+        .map(|s| s.to_string())
+}
@@ -1,126 +1,77 @@
+use crate::language::{FileState, LanguageAnalyzer, ParsedFile};
 use crate::models::*;
+use crate::python_entry_points::EntryPointDetector;
+use crate::python_manifest::PythonManifests;
+use crate::walker::SourceWalker;
 use anyhow::Result;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
 pub struct PythonAnalyzer {
     root_path: PathBuf,
-    modules: Vec<Module>,
-    dependencies: Vec<Dependency>,
-    relationships: Vec<Relationship>,
+    // module id -> name bound by an import -> fully qualified dotted path
+    imports: HashMap<String, HashMap<String, String>>,
+    manifest_errors: Vec<ManifestError>,
 }
 
 impl PythonAnalyzer {
-    pub fn new(root_path: PathBuf) -> Self {
+    pub fn new(root_path: PathBuf, _options: AnalysisOptions) -> Self {
         Self {
             root_path,
-            modules: Vec::new(),
-            dependencies: Vec::new(),
-            relationships: Vec::new(),
+            imports: HashMap::new(),
+            manifest_errors: Vec::new(),
         }
     }
 
-    pub fn analyze(&mut self) -> Result<ProjectStructure> {
-        self.parse_requirements()?;
-        self.walk_python_files()?;
-        self.build_relationships();
-
-        Ok(ProjectStructure {
-            root_path: self.root_path.display().to_string(),
-            modules: self.modules.clone(),
-            dependencies: self.dependencies.clone(),
-            relationships: self.relationships.clone(),
-        })
-    }
-
-    fn parse_requirements(&mut self) -> Result<()> {
-        let req_files = ["requirements.txt", "setup.py", "pyproject.toml"];
-        
-        for req_file in req_files {
-            let path = self.root_path.join(req_file);
-            if path.exists() {
-                if req_file == "requirements.txt" {
-                    let content = fs::read_to_string(&path)?;
-                    for line in content.lines() {
-                        let line = line.trim();
-                        if line.is_empty() || line.starts_with('#') {
-                            continue;
-                        }
-                        
-                        let parts: Vec<&str> = line.split("==").collect();
-                        let name = parts[0].trim().to_string();
-                        let version = parts.get(1).unwrap_or(&"*").trim().to_string();
-                        
-                        self.dependencies.push(Dependency {
-                            name,
-                            version,
-                            dep_type: DependencyType::Normal,
-                        });
-                    }
-                }
-                break;
-            }
-        }
-        Ok(())
-    }
-
-    fn walk_python_files(&mut self) -> Result<()> {
-        for entry in WalkDir::new(&self.root_path)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("py") {
-                let relative_path = path.strip_prefix(&self.root_path).unwrap_or(path);
-                let module_path = self.path_to_module_name(relative_path);
-                
-                if let Ok((module, uses)) = self.parse_python_file(path, &module_path) {
-                    let from_id = module.id.clone();
-                    self.modules.push(module);
-
-                    for use_path in uses {
-                        self.relationships.push(Relationship {
-                            from: from_id.clone(),
-                            to: use_path.replace(".", "_"),
-                            rel_type: RelationType::Uses,
-                        });
-                    }
-                }
-            }
-        }
-        Ok(())
-    }
-
-    fn parse_python_file(&mut self, path: &Path, module_path: &str) -> Result<(Module, Vec<String>)> {
+    fn parse_python_file(&mut self, path: &Path, module_path: &str) -> Result<(Module, Vec<String>, HashMap<String, String>)> {
         let content = fs::read_to_string(path)?;
         let mut items = Vec::new();
         let mut uses = Vec::new();
+        let mut imports = HashMap::new();
+        let mut functions = Vec::new();
+        let mut decorators: Vec<String> = Vec::new();
+        let is_package = path.file_name().and_then(|s| s.to_str()) == Some("__init__.py");
 
-        for line in content.lines() {
+        for line in Self::logical_lines(&content) {
             let line = line.trim();
+
+            if let Some(decorator) = line.strip_prefix('@') {
+                decorators.push(decorator.trim().to_string());
+                continue;
+            }
             
             // Parse imports
             if line.starts_with("import ") || line.starts_with("from ") {
-                if let Some(module) = self.extract_import(line) {
-                    uses.push(module);
+                for module in self.extract_imports(line, module_path, is_package, &mut imports) {
+                    if module != module_path && !uses.contains(&module) {
+                        uses.push(module);
+                    }
                 }
             }
             
             // Parse functions
-            if line.starts_with("def ") {
+            if line.starts_with("def ") || line.starts_with("async def ") {
                 if let Some(func_name) = self.extract_function_name(line) {
                     let visibility = if func_name.starts_with('_') {
                         Visibility::Private
                     } else {
                         Visibility::Public
                     };
-                    
+
+                    functions.push((func_name.clone(), std::mem::take(&mut decorators)));
                     items.push(Item {
                         name: func_name,
                         item_type: ItemType::Function,
                         visibility,
+                        bases: Vec::new(),
+                        binding: None,
+                        span: None,
+                        docs: None,
+                        signature: None,
+                        fields: Vec::new(),
+                        effective_visibility: None,
+                        non_exhaustive: false,
                     });
                 }
             }
@@ -138,37 +89,215 @@ impl PythonAnalyzer {
                         name: class_name,
                         item_type: ItemType::Struct,
                         visibility,
+                        bases: self.extract_class_bases(line),
+                        binding: None,
+                        span: None,
+                        docs: None,
+                        signature: None,
+                        fields: Vec::new(),
+                        effective_visibility: None,
+                        non_exhaustive: false,
                     });
                 }
             }
+
+            if !line.is_empty() && !line.starts_with('#') {
+                decorators.clear();
+            }
+        }
+
+        let mut detector = EntryPointDetector::new(&imports, module_path);
+        for (name, decorators) in &functions {
+            detector.visit_function(name, decorators);
         }
+        let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        detector.visit_source(file_name, &content);
+        let entry_points = detector.into_entry_points();
 
         let module_type = self.determine_module_type(path);
         let id = module_path.replace(".", "_").replace("/", "_");
 
+        // `pkg._internal.helpers` is private by convention; dunder names are not
+        let visibility = if module_path
+            .split('.')
+            .any(|segment| segment.starts_with('_') && !segment.starts_with("__"))
+        {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        };
+
         Ok((
             Module {
                 id,
                 name: module_path.to_string(),
                 path: path.display().to_string(),
                 module_type,
-                visibility: Visibility::Public,
+                visibility,
                 items,
+                entry_points,
+                language: Language::Python,
+                invokes: Vec::new(),
+                impls: Vec::new(),
+                package: None,
+                docs: None,
+                reexports: Vec::new(),
+                lines_of_code: None,
+                churn: None,
+                owners: Vec::new(),
+                primary_authors: Vec::new(),
+                files: Vec::new(),
             },
-            uses
+            uses,
+            imports,
         ))
     }
 
-    fn extract_import(&self, line: &str) -> Option<String> {
-        if line.starts_with("import ") {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            parts.get(1).map(|s| s.to_string())
-        } else if line.starts_with("from ") {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            parts.get(1).map(|s| s.to_string())
-        } else {
-            None
+    // Joins statements whose parentheses span several lines, e.g. multi-line
+    // `from x import (...)`, class headers and decorators
+    fn logical_lines(content: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut pending = String::new();
+        let mut depth: i32 = 0;
+        let mut joined = 0;
+
+        for line in content.lines() {
+            if pending.is_empty() {
+                let trimmed = line.trim_start();
+                let joinable = ["from ", "import ", "class ", "def ", "async def ", "@"]
+                    .iter()
+                    .any(|p| trimmed.starts_with(p));
+                if !joinable {
+                    lines.push(line.to_string());
+                    continue;
+                }
+            } else {
+                pending.push(' ');
+            }
+
+            pending.push_str(line.trim_end_matches('\\'));
+            depth += line.matches(['(', '[']).count() as i32 - line.matches([')', ']']).count() as i32;
+            joined += 1;
+
+            // Give up on runaway statements rather than swallowing the file
+            let continues = line.trim_end().ends_with('\\');
+            if (depth <= 0 && !continues) || joined >= 50 {
+                lines.push(std::mem::take(&mut pending));
+                depth = 0;
+                joined = 0;
+            }
         }
+        if !pending.is_empty() {
+            lines.push(pending);
+        }
+        lines
+    }
+
+    // Records every name an import binds and returns the imported modules
+    fn extract_imports(
+        &self,
+        line: &str,
+        module_path: &str,
+        is_package: bool,
+        imports: &mut HashMap<String, String>,
+    ) -> Vec<String> {
+        let line = line.split('#').next().unwrap_or("").trim();
+
+        if let Some(rest) = line.strip_prefix("import ") {
+            let mut modules = Vec::new();
+            for part in rest.split(',') {
+                let mut words = part.split_whitespace();
+                let Some(module) = words.next() else {
+                    continue;
+                };
+                match (words.next(), words.next()) {
+                    (Some("as"), Some(alias)) => {
+                        imports.insert(alias.to_string(), module.to_string());
+                    }
+                    _ => {
+                        let head = module.split('.').next().unwrap_or(module);
+                        imports.insert(head.to_string(), head.to_string());
+                    }
+                }
+                modules.push(module.to_string());
+            }
+            return modules;
+        }
+
+        if let Some(rest) = line.strip_prefix("from ") {
+            let Some((source, names)) = rest.split_once(" import ") else {
+                return Vec::new();
+            };
+            let Some(source) = Self::resolve_relative(source.trim(), module_path, is_package) else {
+                return Vec::new();
+            };
+
+            let names = names.trim().trim_start_matches('(').trim_end_matches(')');
+            for part in names.split(',') {
+                let mut words = part.split_whitespace();
+                let Some(name) = words.next() else {
+                    continue;
+                };
+                if name == "*" {
+                    continue;
+                }
+                let alias = match (words.next(), words.next()) {
+                    (Some("as"), Some(alias)) => alias,
+                    _ => name,
+                };
+                let qualified = if source.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{}.{}", source, name)
+                };
+                imports.insert(alias.to_string(), qualified);
+            }
+            return if source.is_empty() { Vec::new() } else { vec![source] };
+        }
+
+        Vec::new()
+    }
+
+    // `from ..models import X` inside `pkg.sub.views` refers to `pkg.models`
+    fn resolve_relative(source: &str, module_path: &str, is_package: bool) -> Option<String> {
+        let level = source.chars().take_while(|c| *c == '.').count();
+        if level == 0 {
+            return Some(source.to_string());
+        }
+
+        let mut package: Vec<&str> = module_path.split('.').collect();
+        if !is_package {
+            package.pop();
+        }
+        for _ in 1..level {
+            package.pop()?;
+        }
+
+        let remainder = &source[level..];
+        if !remainder.is_empty() {
+            package.push(remainder);
+        }
+        Some(package.join("."))
+    }
+
+    fn extract_class_bases(&self, line: &str) -> Vec<String> {
+        let Some(start) = line.find('(') else {
+            return Vec::new();
+        };
+        let Some(end) = line.rfind(')') else {
+            return Vec::new();
+        };
+        if end <= start {
+            return Vec::new();
+        }
+
+        line[start + 1..end]
+            .split(',')
+            .map(|b| b.trim())
+            // keyword arguments such as `metaclass=ABCMeta` are not bases
+            .filter(|b| !b.is_empty() && !b.contains('='))
+            .map(|b| b.split('[').next().unwrap_or(b).trim().to_string())
+            .collect()
     }
 
     fn extract_function_name(&self, line: &str) -> Option<String> {
@@ -205,22 +334,158 @@ impl PythonAnalyzer {
             .replace('\\', ".")
     }
 
-    fn build_relationships(&mut self) {
+    // Resolves a base class expression as written in `module_id` to the module
+    // that defines it, following package re-exports a few levels deep
+    fn resolve_class(&self, modules: &[Module], module_id: &str, base: &str) -> Option<(String, String)> {
+        let mut current_module = module_id.to_string();
+        let mut name = base.to_string();
+
+        for _ in 0..5 {
+            let imports = self.imports.get(&current_module);
+            let qualified = match name.split_once('.') {
+                Some((head, tail)) => imports
+                    .and_then(|i| i.get(head))
+                    .map(|q| format!("{}.{}", q, tail))
+                    .unwrap_or_else(|| name.clone()),
+                None => match imports.and_then(|i| i.get(&name)) {
+                    Some(q) => q.clone(),
+                    None => {
+                        let module = modules.iter().find(|m| m.id == current_module)?;
+                        let defines = module
+                            .items
+                            .iter()
+                            .any(|i| i.name == name && i.item_type == ItemType::Struct);
+                        return defines.then_some((current_module, name));
+                    }
+                },
+            };
+
+            let (module_path, class_name) = qualified.rsplit_once('.')?;
+            let target = modules.iter().find(|m| m.name == module_path)?;
+            let defines = target
+                .items
+                .iter()
+                .any(|i| i.name == class_name && i.item_type == ItemType::Struct);
+            if defines {
+                return Some((target.id.clone(), class_name.to_string()));
+            }
+
+            // `from .models import Base` in a package __init__ re-exports it
+            current_module = target.id.clone();
+            name = class_name.to_string();
+        }
+
+        None
+    }
+}
+
+impl LanguageAnalyzer for PythonAnalyzer {
+    fn language(&self) -> Language {
+        Language::Python
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["py"]
+    }
+
+    fn detect(&self) -> bool {
+        let python_markers = ["setup.py", "requirements.txt", "pyproject.toml", "__init__.py"];
+        python_markers
+            .iter()
+            .any(|marker| self.root_path.join(marker).exists())
+    }
+
+    fn parse_dependencies(&mut self) -> Result<Vec<Dependency>> {
+        let (dependencies, errors) = PythonManifests::new(&self.root_path).collect();
+        self.manifest_errors = errors;
+        Ok(dependencies)
+    }
+
+    fn manifest_errors(&mut self) -> Vec<ManifestError> {
+        std::mem::take(&mut self.manifest_errors)
+    }
+
+    fn discover_files(&mut self, walker: &mut SourceWalker) -> Vec<PathBuf> {
+        walker.files(&self.root_path)
+    }
+
+    fn parse_file(&mut self, path: &Path) -> Result<ParsedFile> {
+        let relative_path = path.strip_prefix(&self.root_path).unwrap_or(path);
+        let module_path = self.path_to_module_name(relative_path);
+
+        let (module, uses, imports) = self.parse_python_file(path, &module_path)?;
+        let relationships = uses
+            .into_iter()
+            .map(|use_path| Relationship {
+                from: module.id.clone(),
+                to: use_path.replace(".", "_"),
+                rel_type: RelationType::Uses,
+                label: None,
+            })
+            .collect();
+
+        Ok(ParsedFile {
+            relationships,
+            nested_modules: Vec::new(),
+            state: FileState {
+                imports: vec![(module.id.clone(), imports)],
+                ..FileState::default()
+            },
+            module,
+        })
+    }
+
+    // Module names come from the file's path alone
+    fn parse_context(&self) -> Option<String> {
+        Some(self.root_path.display().to_string())
+    }
+
+    fn add_file(&mut self, state: &FileState) {
+        self.imports.extend(state.imports.iter().cloned());
+    }
+
+    fn build_relationships(&mut self, modules: &[Module]) -> Vec<Relationship> {
+        let mut relationships = Vec::new();
+
         // Build parent-child relationships
-        for module in &self.modules {
+        for module in modules {
             let parts: Vec<&str> = module.name.split('.').collect();
             if parts.len() > 1 {
                 let parent_name = parts[..parts.len() - 1].join(".");
                 let parent_id = parent_name.replace(".", "_");
                 
-                if self.modules.iter().any(|m| m.id == parent_id) {
-                    self.relationships.push(Relationship {
+                if modules.iter().any(|m| m.id == parent_id) {
+                    relationships.push(Relationship {
                         from: parent_id,
                         to: module.id.clone(),
                         rel_type: RelationType::Declares,
+                        label: None,
                     });
                 }
             }
         }
+
+        // Inheritance edges between classes defined in different modules. A
+        // base class from the same module would be a self-loop, so that
+        // inheritance is only recorded in the class item's `bases`.
+        for module in modules {
+            for item in module.items.iter().filter(|i| i.item_type == ItemType::Struct) {
+                for base in &item.bases {
+                    let Some((base_module, base_class)) = self.resolve_class(modules, &module.id, base) else {
+                        continue;
+                    };
+                    if base_module != module.id {
+                        relationships.push(Relationship {
+                            from: module.id.clone(),
+                            to: base_module,
+                            rel_type: RelationType::Inherits,
+                            label: Some(format!("{} -> {}", item.name, base_class)),
+                        });
+                    }
+                }
+            }
+        }
+
+        relationships
     }
 }
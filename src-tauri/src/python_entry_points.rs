@@ -0,0 +1,225 @@
+use crate::models::*;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const HTTP_METHODS: &[&str] = &["get", "post", "put", "delete", "patch", "head", "options"];
+
+// Detects framework entry points in a Python module: Flask/FastAPI route
+// decorators, Django urlpatterns, Click/Typer/argparse commands and pytest fixtures.
+pub struct EntryPointDetector<'a> {
+    imports: &'a HashMap<String, String>,
+    module_path: &'a str,
+    entry_points: Vec<EntryPoint>,
+}
+
+impl<'a> EntryPointDetector<'a> {
+    pub fn new(imports: &'a HashMap<String, String>, module_path: &'a str) -> Self {
+        Self {
+            imports,
+            module_path,
+            entry_points: Vec::new(),
+        }
+    }
+
+    pub fn into_entry_points(self) -> Vec<EntryPoint> {
+        self.entry_points
+    }
+
+    fn imports_package(&self, package: &str) -> bool {
+        self.imports
+            .values()
+            .any(|q| q == package || q.starts_with(&format!("{}.", package)))
+    }
+
+    // Called for every `def` with the decorators written above it
+    pub fn visit_function(&mut self, name: &str, decorators: &[String]) {
+        for decorator in decorators {
+            let (callee, args) = match decorator.find('(') {
+                Some(pos) => (
+                    decorator[..pos].trim(),
+                    decorator[pos + 1..].trim_end().trim_end_matches(')'),
+                ),
+                None => (decorator.trim(), ""),
+            };
+            let attr = callee.rsplit('.').next().unwrap_or(callee);
+            let has_receiver = callee.contains('.');
+
+            if attr == "route" && has_receiver {
+                let methods = string_list_kwarg(args, "methods");
+                self.push_route(name, args, if methods.is_empty() { vec!["GET".to_string()] } else { methods });
+            } else if attr == "api_route" && has_receiver {
+                self.push_route(name, args, string_list_kwarg(args, "methods"));
+            } else if HTTP_METHODS.contains(&attr) && has_receiver && first_string_arg(args).is_some() {
+                self.push_route(name, args, vec![attr.to_uppercase()]);
+            } else if (attr == "command" || attr == "group")
+                && (callee.starts_with("click.") || has_receiver)
+                && (self.imports_package("click") || self.imports_package("typer"))
+            {
+                let framework = if self.imports_package("typer") && !callee.starts_with("click.") {
+                    "typer"
+                } else {
+                    "click"
+                };
+                let command = first_string_arg(args)
+                    .or_else(|| string_kwarg(args, "name"))
+                    .unwrap_or_else(|| name.replace('_', "-"));
+                self.entry_points.push(EntryPoint {
+                    kind: EntryPointKind::CliCommand,
+                    framework: framework.to_string(),
+                    name: command,
+                    handler: Some(name.to_string()),
+                    methods: Vec::new(),
+                });
+            } else if callee == "pytest.fixture"
+                || (callee == "fixture" && self.imports.get("fixture").map(|q| q.as_str()) == Some("pytest.fixture"))
+            {
+                self.entry_points.push(EntryPoint {
+                    kind: EntryPointKind::TestFixture,
+                    framework: "pytest".to_string(),
+                    name: string_kwarg(args, "name").unwrap_or_else(|| name.to_string()),
+                    handler: Some(name.to_string()),
+                    methods: Vec::new(),
+                });
+            }
+        }
+    }
+
+    fn push_route(&mut self, handler: &str, args: &str, methods: Vec<String>) {
+        let Some(route) = first_string_arg(args).or_else(|| string_kwarg(args, "path")) else {
+            return;
+        };
+        let framework = if self.imports_package("fastapi") {
+            "fastapi"
+        } else {
+            "flask"
+        };
+        self.entry_points.push(EntryPoint {
+            kind: EntryPointKind::HttpRoute,
+            framework: framework.to_string(),
+            name: route,
+            handler: Some(handler.to_string()),
+            methods,
+        });
+    }
+
+    // Module-wide patterns that aren't tied to a single function
+    pub fn visit_source(&mut self, file_name: &str, content: &str) {
+        if file_name == "urls.py" {
+            self.detect_django_urls(content);
+        }
+        if self.imports_package("argparse") {
+            self.detect_argparse(content);
+        }
+    }
+
+    fn detect_django_urls(&mut self, content: &str) {
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        let pattern = PATTERN.get_or_init(|| {
+            Regex::new(r#"\b(?:re_path|path|url)\(\s*[rRbBuU]?(?:"([^"]*)"|'([^']*)')\s*,\s*([\w\.]+(?:\([^)]*\))?)"#)
+                .expect("valid django url regex")
+        });
+
+        for caps in pattern.captures_iter(content) {
+            let route = caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str()).unwrap_or("");
+            let handler = caps.get(3).map(|m| m.as_str().to_string());
+            self.entry_points.push(EntryPoint {
+                kind: EntryPointKind::HttpRoute,
+                framework: "django".to_string(),
+                name: format!("/{}", route.trim_start_matches('^').trim_end_matches('$').trim_start_matches('/')),
+                handler,
+                methods: Vec::new(),
+            });
+        }
+    }
+
+    fn detect_argparse(&mut self, content: &str) {
+        static SUBCOMMAND: OnceLock<Regex> = OnceLock::new();
+        static DEFAULTS: OnceLock<Regex> = OnceLock::new();
+        static PROG: OnceLock<Regex> = OnceLock::new();
+        let subcommand = SUBCOMMAND.get_or_init(|| {
+            Regex::new(r#"(?:(\w+)\s*=\s*)?[\w\.]+\.add_parser\(\s*["']([^"']+)["']"#).expect("valid add_parser regex")
+        });
+        let defaults = DEFAULTS.get_or_init(|| {
+            Regex::new(r"(\w+)\.set_defaults\([^)]*\bfunc\s*=\s*([\w\.]+)").expect("valid set_defaults regex")
+        });
+        let prog = PROG.get_or_init(|| {
+            Regex::new(r#"ArgumentParser\((?:[^)]*\bprog\s*=\s*["']([^"']+)["'])?"#).expect("valid ArgumentParser regex")
+        });
+
+        let handlers: HashMap<&str, &str> = defaults
+            .captures_iter(content)
+            .filter_map(|c| Some((c.get(1)?.as_str(), c.get(2)?.as_str())))
+            .collect();
+
+        let mut found_subcommand = false;
+        for caps in subcommand.captures_iter(content) {
+            found_subcommand = true;
+            let handler = caps
+                .get(1)
+                .and_then(|var| handlers.get(var.as_str()))
+                .map(|h| h.to_string());
+            self.entry_points.push(EntryPoint {
+                kind: EntryPointKind::CliCommand,
+                framework: "argparse".to_string(),
+                name: caps[2].to_string(),
+                handler,
+                methods: Vec::new(),
+            });
+        }
+
+        if !found_subcommand {
+            if let Some(caps) = prog.captures(content) {
+                let name = caps
+                    .get(1)
+                    .map(|m| m.as_str().to_string())
+                    .unwrap_or_else(|| self.module_path.to_string());
+                self.entry_points.push(EntryPoint {
+                    kind: EntryPointKind::CliCommand,
+                    framework: "argparse".to_string(),
+                    name,
+                    handler: None,
+                    methods: Vec::new(),
+                });
+            }
+        }
+    }
+}
+
+fn unquote(value: &str) -> Option<String> {
+    let value = value.trim().trim_start_matches(['r', 'R', 'f', 'F', 'b', 'B', 'u', 'U']);
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let rest = &value[1..];
+    let end = rest.find(quote)?;
+    Some(rest[..end].to_string())
+}
+
+fn first_string_arg(args: &str) -> Option<String> {
+    let first = args.split(',').next()?;
+    if first.contains('=') && !first.trim_start().starts_with(['"', '\'']) {
+        return None;
+    }
+    unquote(first)
+}
+
+// Text following `name=` in a call's arguments
+fn kwarg_value<'a>(args: &'a str, name: &str) -> Option<&'a str> {
+    static KWARG: OnceLock<Regex> = OnceLock::new();
+    let kwarg = KWARG.get_or_init(|| Regex::new(r"\b(\w+)\s*=\s*").expect("valid keyword argument regex"));
+    kwarg
+        .captures_iter(args)
+        .find(|caps| &caps[1] == name)
+        .map(|caps| &args[caps.get(0).map_or(0, |m| m.end())..])
+}
+
+fn string_kwarg(args: &str, name: &str) -> Option<String> {
+    unquote(kwarg_value(args, name)?)
+}
+
+fn string_list_kwarg(args: &str, name: &str) -> Vec<String> {
+    let Some(list) = kwarg_value(args, name).and_then(|v| v.strip_prefix(['[', '('])) else {
+        return Vec::new();
+    };
+    let end = list.find([']', ')']).unwrap_or(list.len());
+    list[..end].split(',').filter_map(unquote).map(|m| m.to_uppercase()).collect()
+}
@@ -0,0 +1,623 @@
+use crate::models::*;
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Collects dependencies from every Python manifest format we understand:
+// requirements files (with -r includes), pyproject.toml (PEP 621 and Poetry),
+// setup.cfg, static setup.py arguments and Pipfile.
+pub struct PythonManifests {
+    root_path: PathBuf,
+    dependencies: Vec<Dependency>,
+    seen: HashSet<(String, String)>,
+    visited_files: HashSet<PathBuf>,
+    errors: Vec<ManifestError>,
+}
+
+impl PythonManifests {
+    pub fn new(root_path: &Path) -> Self {
+        Self {
+            root_path: root_path.to_path_buf(),
+            dependencies: Vec::new(),
+            seen: HashSet::new(),
+            visited_files: HashSet::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    // A manifest that fails to parse is reported and skipped, so one broken
+    // file doesn't hide the dependencies declared everywhere else
+    pub fn collect(mut self) -> (Vec<Dependency>, Vec<ManifestError>) {
+        let result = self.parse_pyproject();
+        self.record("pyproject.toml", result);
+        let result = self.parse_setup_cfg();
+        self.record("setup.cfg", result);
+        let result = self.parse_setup_py();
+        self.record("setup.py", result);
+        let result = self.parse_pipfile();
+        self.record("Pipfile", result);
+
+        for path in self.requirements_files() {
+            let dep_type = Self::requirements_file_type(&path);
+            let result = self.parse_requirements_file(&path, dep_type);
+            let relative = path.strip_prefix(&self.root_path).unwrap_or(&path).display().to_string();
+            self.record(&relative, result);
+        }
+
+        (self.dependencies, self.errors)
+    }
+
+    fn record(&mut self, file_name: &str, result: Result<()>) {
+        if let Err(e) = result {
+            eprintln!("Skipping {}: {:#}", file_name, e);
+            self.errors.push(ManifestError {
+                path: file_name.to_string(),
+                message: format!("{:#}", e),
+            });
+        }
+    }
+
+    fn push(&mut self, name: String, version: String, dep_type: DependencyType) {
+        let key = (Self::normalize_name(&name), format!("{:?}", dep_type));
+        if self.seen.insert(key) {
+            self.dependencies.push(Dependency {
+                name,
+                version,
+                dep_type,
+            });
+        }
+    }
+
+    fn push_requirement(&mut self, spec: &str, dep_type: DependencyType) {
+        if let Some((name, version)) = Self::parse_requirement(spec) {
+            self.push(name, version, dep_type);
+        }
+    }
+
+    // PEP 503 normalization, so `Foo_Bar` and `foo-bar` are the same package
+    fn normalize_name(name: &str) -> String {
+        name.to_lowercase().replace(['_', '.'], "-")
+    }
+
+    // Matches whole words of the group name, so `dev-tools` and `test_utils`
+    // are dev groups but `devices` and `latest` are not
+    fn is_dev_group(group: &str) -> bool {
+        const DEV_WORDS: &[&str] = &[
+            "dev", "develop", "development", "test", "tests", "testing", "lint", "doc", "docs",
+        ];
+        group
+            .to_lowercase()
+            .split(['-', '_', '.', ' '])
+            .any(|word| DEV_WORDS.contains(&word))
+    }
+
+    fn group_type(group: &str) -> DependencyType {
+        if Self::is_dev_group(group) {
+            DependencyType::Dev
+        } else {
+            DependencyType::Normal
+        }
+    }
+
+    // Parses a PEP 508 requirement like `requests[socks]>=2.0; python_version < "3.8"`
+    fn parse_requirement(spec: &str) -> Option<(String, String)> {
+        let spec = spec.split(';').next()?.trim();
+        let name_end = spec
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'))
+            .unwrap_or(spec.len());
+        let name = spec[..name_end].to_string();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut rest = spec[name_end..].trim_start();
+        if rest.starts_with('[') {
+            rest = rest.find(']').map(|i| &rest[i + 1..]).unwrap_or("").trim_start();
+        }
+
+        let version = if let Some(url) = rest.strip_prefix('@') {
+            url.trim().to_string()
+        } else {
+            rest.trim_start_matches('(')
+                .trim_end_matches(')')
+                .trim()
+                .to_string()
+        };
+
+        let version = if version.is_empty() { "*".to_string() } else { version };
+        Some((name, version))
+    }
+
+    fn requirements_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        let main = self.root_path.join("requirements.txt");
+        if main.exists() {
+            files.push(main);
+        }
+
+        let mut extra = Vec::new();
+        for dir in [self.root_path.clone(), self.root_path.join("requirements")] {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+                let in_requirements_dir = dir != self.root_path;
+                if path.extension().and_then(|s| s.to_str()) == Some("txt")
+                    && (in_requirements_dir || file_name.starts_with("requirements"))
+                    && path != self.root_path.join("requirements.txt")
+                {
+                    extra.push(path);
+                }
+            }
+        }
+        extra.sort();
+        files.extend(extra);
+        files
+    }
+
+    fn requirements_file_type(path: &Path) -> DependencyType {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        Self::group_type(stem)
+    }
+
+    fn parse_requirements_file(&mut self, path: &Path, dep_type: DependencyType) -> Result<()> {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if !self.visited_files.insert(canonical) {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let base_dir = path.parent().unwrap_or(&self.root_path).to_path_buf();
+
+        let mut logical_line = String::new();
+        for raw in content.lines() {
+            // Backslash continues the requirement on the next line
+            if let Some(part) = raw.strip_suffix('\\') {
+                logical_line.push_str(part);
+                continue;
+            }
+            logical_line.push_str(raw);
+            let line = std::mem::take(&mut logical_line);
+            let line = Self::strip_comment(&line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(include) = Self::option_value(line, &["-r", "--requirement"]) {
+                let include_path = base_dir.join(include);
+                let include_type = match dep_type {
+                    DependencyType::Normal => Self::requirements_file_type(&include_path),
+                    _ => dep_type.clone(),
+                };
+                if include_path.exists() {
+                    self.parse_requirements_file(&include_path, include_type)?;
+                } else {
+                    eprintln!("Included requirements file not found: {}", include_path.display());
+                }
+                continue;
+            }
+
+            if let Some(editable) = Self::option_value(line, &["-e", "--editable"]) {
+                if let Some(name) = Self::egg_name(editable) {
+                    self.push(name, editable.to_string(), dep_type.clone());
+                }
+                continue;
+            }
+
+            // Other pip options (--index-url, -c constraints, ...) are not dependencies
+            if line.starts_with('-') {
+                continue;
+            }
+
+            // A bare URL, as opposed to PEP 508 `name @ url`; its userinfo
+            // (`git@github.com`) may contain an `@` of its own
+            if Self::is_url(line) {
+                if let Some(name) = Self::egg_name(line) {
+                    self.push(name, line.to_string(), dep_type.clone());
+                }
+                continue;
+            }
+
+            self.push_requirement(line, dep_type.clone());
+        }
+
+        Ok(())
+    }
+
+    fn strip_comment(line: &str) -> &str {
+        if line.trim_start().starts_with('#') {
+            return "";
+        }
+        match line.find(" #").or_else(|| line.find("\t#")) {
+            Some(pos) => &line[..pos],
+            None => line,
+        }
+    }
+
+    // Matches `-r file`, `-rfile`, `--requirement file` and `--requirement=file`
+    fn option_value<'a>(line: &'a str, names: &[&str]) -> Option<&'a str> {
+        for name in names {
+            if let Some(rest) = line.strip_prefix(name) {
+                let value = if name.starts_with("--") {
+                    if rest.starts_with('=') || rest.starts_with(char::is_whitespace) {
+                        rest.trim_start_matches('=').trim()
+                    } else {
+                        continue;
+                    }
+                } else {
+                    rest.trim()
+                };
+                if !value.is_empty() {
+                    return Some(value);
+                }
+            }
+        }
+        None
+    }
+
+    fn is_url(line: &str) -> bool {
+        line.split_once("://").is_some_and(|(scheme, _)| {
+            !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        })
+    }
+
+    fn egg_name(url: &str) -> Option<String> {
+        let fragment = url.split("#egg=").nth(1)?;
+        let name = fragment.split(['&', '[']).next()?.trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    fn read_toml(&self, file_name: &str) -> Result<Option<toml::Table>> {
+        let path = self.root_path.join(file_name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", file_name))?;
+        let table = content
+            .parse::<toml::Table>()
+            .with_context(|| format!("Failed to parse {}", file_name))?;
+        Ok(Some(table))
+    }
+
+    fn parse_pyproject(&mut self) -> Result<()> {
+        let Some(pyproject) = self.read_toml("pyproject.toml")? else {
+            return Ok(());
+        };
+
+        if let Some(project) = pyproject.get("project").and_then(|v| v.as_table()) {
+            for spec in Self::string_array(project.get("dependencies")) {
+                self.push_requirement(&spec, DependencyType::Normal);
+            }
+            if let Some(optional) = project.get("optional-dependencies").and_then(|v| v.as_table()) {
+                for (group, specs) in optional {
+                    for spec in Self::string_array(Some(specs)) {
+                        self.push_requirement(&spec, Self::group_type(group));
+                    }
+                }
+            }
+        }
+
+        // PEP 735 dependency groups
+        if let Some(groups) = pyproject.get("dependency-groups").and_then(|v| v.as_table()) {
+            for (group, specs) in groups {
+                for spec in Self::string_array(Some(specs)) {
+                    self.push_requirement(&spec, Self::group_type(group));
+                }
+            }
+        }
+
+        if let Some(build_system) = pyproject.get("build-system").and_then(|v| v.as_table()) {
+            for spec in Self::string_array(build_system.get("requires")) {
+                self.push_requirement(&spec, DependencyType::Build);
+            }
+        }
+
+        let poetry = pyproject
+            .get("tool")
+            .and_then(|v| v.get("poetry"))
+            .and_then(|v| v.as_table());
+        if let Some(poetry) = poetry {
+            if let Some(deps) = poetry.get("dependencies").and_then(|v| v.as_table()) {
+                self.push_poetry_table(deps, DependencyType::Normal);
+            }
+            if let Some(deps) = poetry.get("dev-dependencies").and_then(|v| v.as_table()) {
+                self.push_poetry_table(deps, DependencyType::Dev);
+            }
+            if let Some(groups) = poetry.get("group").and_then(|v| v.as_table()) {
+                for (group, body) in groups {
+                    if let Some(deps) = body.get("dependencies").and_then(|v| v.as_table()) {
+                        self.push_poetry_table(deps, Self::group_type(group));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Poetry and Pipfile share the `name = "version"` / `name = { version = ... }` shape
+    fn push_poetry_table(&mut self, deps: &toml::Table, dep_type: DependencyType) {
+        for (name, spec) in deps {
+            if name == "python" {
+                continue;
+            }
+            let version = match spec {
+                toml::Value::String(v) => v.clone(),
+                toml::Value::Table(t) => t
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+                    .or_else(|| t.get("git").and_then(|v| v.as_str()).map(|v| v.to_string()))
+                    .or_else(|| t.get("path").and_then(|v| v.as_str()).map(|v| v.to_string()))
+                    .unwrap_or_else(|| "*".to_string()),
+                _ => "*".to_string(),
+            };
+            self.push(name.clone(), version, dep_type.clone());
+        }
+    }
+
+    fn string_array(value: Option<&toml::Value>) -> Vec<String> {
+        value
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn parse_pipfile(&mut self) -> Result<()> {
+        let Some(pipfile) = self.read_toml("Pipfile")? else {
+            return Ok(());
+        };
+
+        if let Some(deps) = pipfile.get("packages").and_then(|v| v.as_table()) {
+            self.push_poetry_table(deps, DependencyType::Normal);
+        }
+        if let Some(deps) = pipfile.get("dev-packages").and_then(|v| v.as_table()) {
+            self.push_poetry_table(deps, DependencyType::Dev);
+        }
+        Ok(())
+    }
+
+    fn parse_setup_cfg(&mut self) -> Result<()> {
+        let path = self.root_path.join("setup.cfg");
+        if !path.exists() {
+            return Ok(());
+        }
+        let content = fs::read_to_string(&path).context("Failed to read setup.cfg")?;
+        let sections = Self::parse_ini(&content);
+
+        if let Some(options) = sections.get("options") {
+            for (key, value) in options {
+                let dep_type = match key.as_str() {
+                    "install_requires" => DependencyType::Normal,
+                    "tests_require" => DependencyType::Dev,
+                    "setup_requires" => DependencyType::Build,
+                    _ => continue,
+                };
+                self.push_cfg_value(value, dep_type)?;
+            }
+        }
+
+        if let Some(extras) = sections.get("options.extras_require") {
+            for (group, value) in extras {
+                self.push_cfg_value(value, Self::group_type(group))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push_cfg_value(&mut self, value: &str, dep_type: DependencyType) -> Result<()> {
+        // setuptools allows `install_requires = file: requirements.in`
+        if let Some(files) = value.trim().strip_prefix("file:") {
+            for file in files.split(',') {
+                let path = self.root_path.join(file.trim());
+                if path.exists() {
+                    self.parse_requirements_file(&path, dep_type.clone())?;
+                }
+            }
+            return Ok(());
+        }
+
+        for line in value.lines().flat_map(|l| l.split(',')) {
+            let line = Self::strip_comment(line).trim();
+            if !line.is_empty() {
+                self.push_requirement(line, dep_type.clone());
+            }
+        }
+        Ok(())
+    }
+
+    // Minimal INI reader: sections, `key = value` and indented continuation lines
+    fn parse_ini(content: &str) -> HashMap<String, Vec<(String, String)>> {
+        let mut sections: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        let mut current_section = String::new();
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+                continue;
+            }
+
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                current_section = trimmed[1..trimmed.len() - 1].trim().to_string();
+                continue;
+            }
+
+            let entries = sections.entry(current_section.clone()).or_default();
+            let is_continuation = line.starts_with(char::is_whitespace);
+            match entries.last_mut() {
+                Some((_, value)) if is_continuation => {
+                    value.push('\n');
+                    value.push_str(trimmed);
+                }
+                _ => {
+                    if let Some((key, value)) = trimmed.split_once(['=', ':']) {
+                        entries.push((key.trim().to_string(), value.trim().to_string()));
+                    }
+                }
+            }
+        }
+
+        sections
+    }
+
+    fn parse_setup_py(&mut self) -> Result<()> {
+        let path = self.root_path.join("setup.py");
+        if !path.exists() {
+            return Ok(());
+        }
+        let content = fs::read_to_string(&path).context("Failed to read setup.py")?;
+
+        let keywords = [
+            ("install_requires", DependencyType::Normal),
+            ("tests_require", DependencyType::Dev),
+            ("setup_requires", DependencyType::Build),
+        ];
+        for (keyword, dep_type) in keywords {
+            if let Some(list) = Self::keyword_literal(&content, keyword, '[', ']') {
+                for spec in Self::string_literals(list) {
+                    self.push_requirement(&spec, dep_type.clone());
+                }
+            }
+        }
+
+        if let Some(extras) = Self::keyword_literal(&content, "extras_require", '{', '}') {
+            for (group, list) in Self::dict_entries(extras) {
+                for spec in Self::string_literals(list) {
+                    self.push_requirement(&spec, Self::group_type(&group));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Finds `keyword=<open>...<close>` and returns the literal's body. Anything
+    // that isn't a literal (a variable, a function call) is not statically known.
+    fn keyword_literal<'a>(content: &'a str, keyword: &str, open: char, close: char) -> Option<&'a str> {
+        let mut search_from = 0;
+        while let Some(pos) = content[search_from..].find(keyword) {
+            let after = &content[search_from + pos + keyword.len()..];
+            search_from += pos + keyword.len();
+
+            let Some(value) = after.trim_start().strip_prefix('=') else {
+                continue;
+            };
+            let value = value.trim_start();
+            if value.starts_with(open) {
+                return Self::matching_body(value, open, close);
+            }
+        }
+        None
+    }
+
+    fn matching_body(src: &str, open: char, close: char) -> Option<&str> {
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+
+        for (i, c) in src.char_indices() {
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+                continue;
+            }
+            match c {
+                '\'' | '"' => quote = Some(c),
+                c if c == open => depth += 1,
+                c if c == close => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&src[open.len_utf8()..i]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn string_literals(src: &str) -> Vec<String> {
+        let mut literals = Vec::new();
+        let mut chars = src.chars();
+        while let Some(c) = chars.next() {
+            if c == '#' {
+                // Skip comments up to the end of the line
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            if c == '\'' || c == '"' {
+                let quote = c;
+                let mut literal = String::new();
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    if escaped {
+                        literal.push(c);
+                        escaped = false;
+                    } else if c == '\\' {
+                        escaped = true;
+                    } else if c == quote {
+                        break;
+                    } else {
+                        literal.push(c);
+                    }
+                }
+                literals.push(literal);
+            }
+        }
+        literals
+    }
+
+    // `{'dev': [...], "test": [...]}` -> [("dev", "..."), ("test", "...")]
+    fn dict_entries(src: &str) -> Vec<(String, &str)> {
+        let mut entries = Vec::new();
+        let mut rest = src;
+
+        while let Some(start) = rest.find(['\'', '"']) {
+            let quote = rest[start..].chars().next().unwrap_or('"');
+            let after_key = &rest[start + 1..];
+            let Some(key_end) = after_key.find(quote) else {
+                break;
+            };
+            let key = after_key[..key_end].to_string();
+            let after = after_key[key_end + 1..].trim_start();
+
+            let Some(value) = after.strip_prefix(':') else {
+                rest = &after_key[key_end + 1..];
+                continue;
+            };
+            let value = value.trim_start();
+            match Self::matching_body(value, '[', ']') {
+                Some(body) if value.starts_with('[') => {
+                    entries.push((key, body));
+                    // body is a subslice of value; skip past its closing bracket
+                    let consumed = body.len() + 2;
+                    rest = &value[consumed.min(value.len())..];
+                }
+                _ => rest = value,
+            }
+        }
+
+        entries
+    }
+}
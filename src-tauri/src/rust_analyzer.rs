@@ -0,0 +1,277 @@
+use crate::language::{FileState, LanguageAnalyzer, ParsedFile};
+use crate::models::*;
+use crate::parser::RustParser;
+use crate::walker::SourceWalker;
+use anyhow::{Context, Result};
+use cargo_metadata::MetadataCommand;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub struct RustAnalyzer {
+    crate_root: PathBuf,
+    // Workspace members: package name and directory
+    packages: Vec<(String, PathBuf)>,
+    // Submodule declarations per module name
+    mod_decls: HashMap<String, Vec<(String, Visibility)>>,
+    // Names brought in by `use` per module id, resolved once every module is known
+    uses: HashMap<String, Vec<String>>,
+}
+
+impl RustAnalyzer {
+    pub fn new(root_path: PathBuf, _options: AnalysisOptions) -> Self {
+        // Tauri apps keep their crate in `src-tauri` next to the frontend
+        let tauri_root = root_path.join("src-tauri");
+        let crate_root = if !root_path.join("Cargo.toml").exists() && tauri_root.join("Cargo.toml").exists() {
+            tauri_root
+        } else {
+            root_path
+        };
+        Self {
+            crate_root,
+            packages: Vec::new(),
+            mod_decls: HashMap::new(),
+            uses: HashMap::new(),
+        }
+    }
+
+    fn path_to_module_name(&self, path: &Path) -> String {
+        let path_str = path.to_string_lossy();
+        let path_str = path_str
+            .trim_start_matches("src/")
+            .trim_start_matches("tests/")
+            .trim_start_matches("examples/")
+            .trim_start_matches("benches/")
+            .trim_end_matches(".rs");
+
+        path_str
+            .replace("/mod", "")
+            .replace("/", "::")
+            .replace("\\", "::")
+    }
+
+    fn is_member_crate(&self, name: &str) -> bool {
+        self.packages.iter().any(|(package, _)| package.replace('-', "_") == name)
+    }
+}
+
+impl LanguageAnalyzer for RustAnalyzer {
+    fn language(&self) -> Language {
+        Language::Rust
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rs"]
+    }
+
+    fn detect(&self) -> bool {
+        self.crate_root.join("Cargo.toml").exists()
+    }
+
+    fn parse_dependencies(&mut self) -> Result<Vec<Dependency>> {
+        let metadata = MetadataCommand::new()
+            .manifest_path(self.crate_root.join("Cargo.toml"))
+            .exec()
+            .context("Failed to execute cargo metadata")?;
+
+        self.packages = metadata
+            .workspace_packages()
+            .iter()
+            .filter_map(|p| {
+                let dir = p.manifest_path.parent()?;
+                Some((p.name.clone(), PathBuf::from(dir.as_std_path())))
+            })
+            .collect();
+
+        let mut dependencies = Vec::new();
+        for package in &metadata.packages {
+            for dep in &package.dependencies {
+                let dep_type = match dep.kind {
+                    cargo_metadata::DependencyKind::Normal => DependencyType::Normal,
+                    cargo_metadata::DependencyKind::Development => DependencyType::Dev,
+                    cargo_metadata::DependencyKind::Build => DependencyType::Build,
+                    _ => DependencyType::Normal,
+                };
+
+                dependencies.push(Dependency {
+                    name: dep.name.clone(),
+                    version: dep.req.to_string(),
+                    dep_type,
+                });
+            }
+        }
+
+        Ok(dependencies)
+    }
+
+    fn discover_files(&mut self, walker: &mut SourceWalker) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = self.packages.iter().map(|(_, dir)| dir.clone()).collect();
+        if roots.is_empty() {
+            roots.push(self.crate_root.clone());
+        }
+
+        // Per crate: sources first, then tests, examples, benches
+        roots
+            .iter()
+            .flat_map(|root| {
+                ["src", "tests", "examples", "benches"]
+                    .iter()
+                    .map(move |dir| root.join(dir))
+            })
+            .flat_map(|dir| walker.files(&dir))
+            .collect()
+    }
+
+    fn parse_file(&mut self, path: &Path) -> Result<ParsedFile> {
+        // The innermost workspace member containing the file
+        let package = self
+            .packages
+            .iter()
+            .filter(|(_, dir)| path.starts_with(dir))
+            .max_by_key(|(_, dir)| dir.components().count())
+            .cloned();
+        let package_root = package.as_ref().map_or(&self.crate_root, |(_, dir)| dir);
+
+        let relative_path = path
+            .strip_prefix(package_root)
+            .unwrap_or(path)
+            .to_path_buf();
+
+        // In a multi-crate workspace module names are qualified by crate
+        let mut module_path = self.path_to_module_name(&relative_path);
+        if let Some((name, _)) = package.as_ref().filter(|_| self.packages.len() > 1) {
+            module_path = format!("{}::{}", name.replace('-', "_"), module_path);
+        }
+
+        let mut parser = RustParser::new();
+        let mut module = parser.parse_file(path, &module_path)?;
+        module.package = package.map(|(name, _)| name);
+
+        let mut state = FileState::default();
+        state.mod_decls.push((module.name.clone(), parser.get_mod_decls()));
+        if relative_path.starts_with("src") {
+            state.uses.push((module.id.clone(), parser.get_uses()));
+        }
+
+        let mut nested_modules = Vec::new();
+        for (mut child, decls) in parser.get_nested_modules() {
+            child.package = module.package.clone();
+            state.mod_decls.push((child.name.clone(), decls));
+            nested_modules.push(child);
+        }
+
+        Ok(ParsedFile {
+            module,
+            relationships: Vec::new(),
+            nested_modules,
+            state,
+        })
+    }
+
+    // Module names come from the file's path within its workspace member
+    fn parse_context(&self) -> Option<String> {
+        Some(format!("{:?} {:?}", self.crate_root, self.packages))
+    }
+
+    fn add_file(&mut self, state: &FileState) {
+        self.mod_decls.extend(state.mod_decls.iter().cloned());
+        self.uses.extend(state.uses.iter().cloned());
+    }
+
+    fn resolve_modules(&mut self, modules: &mut [Module]) {
+        let packages: HashMap<String, Option<String>> = modules
+            .iter()
+            .map(|m| (m.name.clone(), m.package.clone()))
+            .collect();
+
+        for module in modules.iter_mut().filter(|m| m.module_type == ModuleType::Module) {
+            let (parent, name) = match module.name.rsplit_once("::") {
+                Some((parent, name)) => (Some(parent), name),
+                None => (None, module.name.as_str()),
+            };
+
+            // `a::b` is declared by `a`; top-level modules by the crate root,
+            // preferring lib.rs when a crate also has a main.rs
+            let in_package = |candidate: &String| packages.get(candidate) == Some(&module.package);
+            let declarer = parent
+                .map(str::to_string)
+                .filter(in_package)
+                .into_iter()
+                .chain(["lib", "main"].iter().map(|root| match parent {
+                    Some(parent) => format!("{}::{}", parent, root),
+                    None => root.to_string(),
+                }))
+                .filter(in_package)
+                .find_map(|candidate| {
+                    self.mod_decls
+                        .get(&candidate)?
+                        .iter()
+                        .find(|(declared, _)| declared == name)
+                        .map(|(_, visibility)| visibility.clone())
+                });
+
+            // Undeclared files are not compiled into the crate
+            module.visibility = declarer.unwrap_or(Visibility::Private);
+        }
+    }
+
+    fn build_relationships(&mut self, modules: &[Module]) -> Vec<Relationship> {
+        // Build parent-child relationships for modules
+        let mut relationships = Vec::new();
+        let mut module_map: HashMap<String, String> = HashMap::new();
+
+        for module in modules {
+            module_map.insert(module.id.clone(), module.name.clone());
+        }
+
+        for module in modules {
+            let parts: Vec<&str> = module.name.split("::").collect();
+            if parts.len() > 1 {
+                let parent_name = parts[..parts.len() - 1].join("::");
+                let parent_id = parent_name.replace("::", "_");
+
+                if module_map.contains_key(&parent_id) {
+                    relationships.push(Relationship {
+                        from: parent_id,
+                        to: module.id.clone(),
+                        rel_type: RelationType::Declares,
+                        label: None,
+                    });
+                }
+            }
+        }
+
+        // `use other_member::...` points at that crate's root and `use foo`
+        // at the using crate's own `foo`; anything else (std, dependencies)
+        // is kept as written
+        let ids: HashSet<&str> = modules.iter().map(|m| m.id.as_str()).collect();
+        for module in modules {
+            let Some(uses) = self.uses.get(&module.id) else {
+                continue;
+            };
+            let crate_prefix = match &module.package {
+                Some(name) if self.packages.len() > 1 => Some(name.replace('-', "_")),
+                _ => None,
+            };
+            for target in uses {
+                let local = crate_prefix.as_ref().map(|prefix| format!("{}_{}", prefix, target));
+                let to = if crate_prefix.is_some() && self.is_member_crate(target) {
+                    ["lib", "main"]
+                        .iter()
+                        .map(|root| format!("{}_{}", target, root))
+                        .find(|id| ids.contains(id.as_str()))
+                        .unwrap_or_else(|| target.clone())
+                } else {
+                    local.filter(|id| ids.contains(id.as_str())).unwrap_or_else(|| target.clone())
+                };
+                relationships.push(Relationship {
+                    from: module.id.clone(),
+                    to,
+                    rel_type: RelationType::Uses,
+                    label: None,
+                });
+            }
+        }
+
+        relationships
+    }
+}
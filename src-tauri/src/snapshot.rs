@@ -0,0 +1,87 @@
+use crate::analyzer::{self, ProjectAnalyzer};
+use crate::git_history;
+use crate::language;
+use crate::models::*;
+use crate::public_api;
+use anyhow::{bail, Context, Result};
+use git2::Repository;
+use std::fs;
+use std::path::Path;
+
+// Bumped when snapshots change in a way older readers would misinterpret
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+pub fn create(structure: ProjectStructure, git_commit: Option<String>) -> Snapshot {
+    let metrics = ProjectAnalyzer::new(structure.clone()).calculate_metrics();
+    Snapshot {
+        format_version: SNAPSHOT_VERSION,
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now(),
+        git_commit,
+        problems: analyzer::find_problems(&structure),
+        metrics,
+        structure,
+    }
+}
+
+// Writes the analysis with its problems, metrics and the commit it was made at
+pub fn save(structure: &ProjectStructure, path: &Path) -> Result<Snapshot> {
+    let snapshot = create(structure.clone(), git_commit(Path::new(&structure.root_path)));
+    write(&snapshot, path)?;
+    Ok(snapshot)
+}
+
+pub fn write(snapshot: &Snapshot, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(snapshot).context("Failed to serialize snapshot")?;
+    fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+// Reads a snapshot, or a bare ProjectStructure saved as JSON before snapshots
+// existed
+pub fn load(path: &Path) -> Result<Snapshot> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Not a JSON file: {}", path.display()))?;
+
+    let Some(version) = value.get("format_version") else {
+        let mut structure: ProjectStructure = serde_json::from_value(value)
+            .with_context(|| format!("Not a saved analysis: {}", path.display()))?;
+        if structure.public_api.is_empty() {
+            public_api::link_public_api(&mut structure, path);
+        }
+        return Ok(create(structure, None));
+    };
+
+    let version = version.as_u64().context("Invalid snapshot format version")?;
+    if version > SNAPSHOT_VERSION as u64 {
+        bail!(
+            "Snapshot format {} is newer than this version supports ({}); update the visualizer",
+            version,
+            SNAPSHOT_VERSION
+        );
+    }
+    serde_json::from_value(value).with_context(|| format!("Invalid snapshot: {}", path.display()))
+}
+
+// One side of a diff: a project directory to analyze, a saved snapshot, or
+// `<directory>@<revision>` for the project at a git commit, tag or branch
+pub fn load_structure(spec: &str, options: &AnalysisOptions) -> Result<ProjectStructure> {
+    let path = Path::new(spec);
+    if path.is_dir() {
+        language::analyze_project(path, options)
+    } else if path.is_file() {
+        Ok(load(path)?.structure)
+    } else if let Some((dir, revision)) = git_history::parse_spec(spec) {
+        Ok(git_history::analyze_revision(dir, revision, options)?.0)
+    } else {
+        bail!("Not a directory, snapshot or <directory>@<revision>: {}", spec)
+    }
+}
+
+// HEAD of the git repository containing `root`, if any
+pub fn git_commit(root: &Path) -> Option<String> {
+    let repo = Repository::discover(root).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
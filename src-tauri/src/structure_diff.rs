@@ -0,0 +1,463 @@
+use crate::analyzer::{self, ProjectAnalyzer};
+use crate::export::internal_relationships;
+use crate::models::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+// Minimum share of common items for an unmatched pair of modules to count as
+// the same module moved or renamed
+const RENAME_SIMILARITY: f64 = 0.5;
+
+// How many entries of a list the Markdown summary shows
+const MARKDOWN_LIST_LIMIT: usize = 25;
+
+// Compares two analyses of the same project. Modules are matched by id;
+// those left over are paired by their items to detect moves and renames,
+// so that relationships, cycles and metrics compare across the rename.
+pub fn diff(old: &ProjectStructure, new: &ProjectStructure) -> StructureDiff {
+    let new_ids: HashSet<&str> = new.modules.iter().map(|m| m.id.as_str()).collect();
+    let old_ids: HashSet<&str> = old.modules.iter().map(|m| m.id.as_str()).collect();
+    let removed: Vec<&Module> = old.modules.iter().filter(|m| !new_ids.contains(m.id.as_str())).collect();
+    let added: Vec<&Module> = new.modules.iter().filter(|m| !old_ids.contains(m.id.as_str())).collect();
+
+    // Old id -> new id, for every module present in both versions
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    let mut candidates = Vec::new();
+    for old_module in &removed {
+        for new_module in &added {
+            let similarity = item_similarity(old_module, new_module);
+            if old_module.language == new_module.language && similarity >= RENAME_SIMILARITY {
+                candidates.push((similarity, *old_module, *new_module));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let mut paired_new = HashSet::new();
+    for (_, old_module, new_module) in candidates {
+        if !renamed.contains_key(&old_module.id) && paired_new.insert(new_module.id.as_str()) {
+            renamed.insert(old_module.id.clone(), new_module.id.clone());
+        }
+    }
+    let map_id = |id: &str| renamed.get(id).cloned().unwrap_or_else(|| id.to_string());
+
+    let mut modules = Vec::new();
+    let old_by_id: HashMap<String, &Module> = old.modules.iter().map(|m| (map_id(&m.id), m)).collect();
+    for module in &new.modules {
+        let Some(before) = old_by_id.get(&module.id) else {
+            modules.push(module_change(ChangeKind::Added, module, None, new));
+            continue;
+        };
+        let kind = if before.id != module.id {
+            if parent_name(before) == parent_name(module) {
+                ChangeKind::Renamed
+            } else {
+                ChangeKind::Moved
+            }
+        } else if relative_path(old, before) != relative_path(new, module) {
+            ChangeKind::Moved
+        } else {
+            ChangeKind::Changed
+        };
+        let change = module_change(kind, module, Some((old, before)), new);
+        if change.kind != ChangeKind::Changed || !change.details.is_empty() || !change.item_changes.is_empty() {
+            modules.push(change);
+        }
+    }
+    for module in old.modules.iter().filter(|m| !renamed.contains_key(&m.id) && !new_ids.contains(m.id.as_str())) {
+        modules.push(module_change(ChangeKind::Removed, module, None, old));
+    }
+
+    // Relationships between modules, with old ids translated to the new ones
+    let old_edges: BTreeSet<(String, String, String)> = internal_relationships(old)
+        .map(|r| (map_id(&r.from), map_id(&r.to), relation_key(&r.rel_type)))
+        .collect();
+    let new_edges: BTreeSet<(String, String, String)> = internal_relationships(new)
+        .map(|r| (r.from.clone(), r.to.clone(), relation_key(&r.rel_type)))
+        .collect();
+    let mut relationships = Vec::new();
+    let mut removed_relationships = Vec::new();
+    let mut seen = HashSet::new();
+    for rel in internal_relationships(new) {
+        let key = (rel.from.clone(), rel.to.clone(), relation_key(&rel.rel_type));
+        if !old_edges.contains(&key) && seen.insert(key) {
+            relationships.push(relationship_change(ChangeKind::Added, rel.from.clone(), rel.to.clone(), rel));
+        }
+    }
+    for rel in internal_relationships(old) {
+        let (from, to) = (map_id(&rel.from), map_id(&rel.to));
+        let key = (from.clone(), to.clone(), relation_key(&rel.rel_type));
+        if !new_edges.contains(&key) && seen.insert(key) {
+            relationships.push(relationship_change(ChangeKind::Removed, from.clone(), to.clone(), rel));
+            removed_relationships.push(Relationship {
+                from,
+                to,
+                rel_type: rel.rel_type.clone(),
+                label: rel.label.clone(),
+            });
+        }
+    }
+
+    let old_problems = analyzer::find_problems(old);
+    let new_problems = analyzer::find_problems(new);
+    let old_cycles: BTreeSet<Vec<String>> = old_problems
+        .cycles
+        .iter()
+        .map(|c| normalize_cycle(c.iter().map(|id| map_id(id)).collect()))
+        .collect();
+    let new_cycles: BTreeSet<Vec<String>> = new_problems.cycles.iter().map(|c| normalize_cycle(c.clone())).collect();
+
+    let old_metrics = ProjectAnalyzer::new(old.clone()).calculate_metrics();
+    let new_metrics = ProjectAnalyzer::new(new.clone()).calculate_metrics();
+    let mut metric_deltas: Vec<MetricDelta> = new
+        .modules
+        .iter()
+        .filter_map(|module| {
+            let before = old_metrics.get(&old_by_id.get(&module.id)?.id)?;
+            let after = new_metrics.get(&module.id)?;
+            let delta = MetricDelta {
+                module_id: module.id.clone(),
+                name: module.name.clone(),
+                lines_of_code: after.lines_of_code as i64 - before.lines_of_code as i64,
+                incoming_deps: after.incoming_deps as i64 - before.incoming_deps as i64,
+                outgoing_deps: after.outgoing_deps as i64 - before.outgoing_deps as i64,
+                complexity_score: after.complexity_score as i64 - before.complexity_score as i64,
+            };
+            let changed = delta.lines_of_code != 0
+                || delta.incoming_deps != 0
+                || delta.outgoing_deps != 0
+                || delta.complexity_score != 0;
+            changed.then_some(delta)
+        })
+        .collect();
+    metric_deltas.sort_by_key(|d| std::cmp::Reverse(d.lines_of_code.abs()));
+
+    let lines = |metrics: &HashMap<String, ModuleMetrics>| metrics.values().map(|m| m.lines_of_code).sum();
+    let totals = vec![
+        stat("Modules", old.modules.len(), new.modules.len()),
+        stat("Relationships", old_edges.len(), new_edges.len()),
+        stat("Lines of code", lines(&old_metrics), lines(&new_metrics)),
+        stat("Cycles", old_problems.cycles.len(), new_problems.cycles.len()),
+        stat("Public API paths", old.public_api.len(), new.public_api.len()),
+    ];
+
+    let mut graph = new.clone();
+    graph.modules.extend(
+        old.modules
+            .iter()
+            .filter(|m| !renamed.contains_key(&m.id) && !new_ids.contains(m.id.as_str()))
+            .cloned(),
+    );
+    graph.relationships.extend(removed_relationships);
+
+    let mut diff = StructureDiff {
+        totals,
+        modules,
+        relationships,
+        new_cycles: new_cycles.difference(&old_cycles).cloned().collect(),
+        resolved_cycles: old_cycles.difference(&new_cycles).cloned().collect(),
+        metric_deltas,
+        graph,
+        markdown: String::new(),
+    };
+    diff.markdown = render_markdown(&diff);
+    diff
+}
+
+// Share of (name, kind) item pairs the modules have in common
+fn item_similarity(old: &Module, new: &Module) -> f64 {
+    let items = |m: &Module| -> HashSet<(String, String)> {
+        m.items
+            .iter()
+            .map(|i| (i.name.clone(), format!("{:?}", i.item_type)))
+            .collect()
+    };
+    let (old_items, new_items) = (items(old), items(new));
+    let union = old_items.union(&new_items).count();
+    if union == 0 {
+        return 0.0;
+    }
+    old_items.intersection(&new_items).count() as f64 / union as f64
+}
+
+// Paths relative to the project root, so checkouts in different places
+// compare. Modules spanning several files (Go packages) are located by their
+// directory, which doesn't move when files are added. Both paths are
+// canonicalized when they exist, as a root of `.` may hold absolute paths.
+fn relative_path(structure: &ProjectStructure, module: &Module) -> String {
+    let dir = match module.files.is_empty() {
+        true => module.path.as_str(),
+        false => module.path.rsplit_once(['/', '\\']).map_or(module.path.as_str(), |(dir, _)| dir),
+    };
+    let (root, path) = (Path::new(&structure.root_path), Path::new(dir));
+    let rest = match (root.canonicalize(), path.canonicalize()) {
+        (Ok(root), Ok(path)) => path.strip_prefix(root).ok().map(Path::to_path_buf),
+        _ => None,
+    };
+    match rest.or_else(|| path.strip_prefix(root).ok().map(Path::to_path_buf)) {
+        Some(rest) => rest.to_string_lossy().replace('\\', "/"),
+        None => dir.to_string(),
+    }
+}
+
+fn parent_name(module: &Module) -> String {
+    let segments = module.name_segments();
+    segments[..segments.len() - 1].join(module.name_separator())
+}
+
+fn relation_key(rel_type: &RelationType) -> String {
+    format!("{:?}", rel_type)
+}
+
+fn relationship_change(kind: ChangeKind, from: String, to: String, rel: &Relationship) -> RelationshipChange {
+    RelationshipChange {
+        kind,
+        from,
+        to,
+        rel_type: rel.rel_type.clone(),
+    }
+}
+
+fn stat(name: &str, before: usize, after: usize) -> StatDelta {
+    StatDelta {
+        name: name.to_string(),
+        before,
+        after,
+    }
+}
+
+// The same cycle can be reported from any of its modules; start it at the
+// smallest id so both versions list it identically
+fn normalize_cycle(mut cycle: Vec<String>) -> Vec<String> {
+    if cycle.len() > 1 && cycle.first() == cycle.last() {
+        cycle.pop();
+    }
+    if let Some(start) = cycle.iter().enumerate().min_by_key(|(_, id)| *id).map(|(i, _)| i) {
+        cycle.rotate_left(start);
+    }
+    cycle
+}
+
+fn module_change(
+    kind: ChangeKind,
+    module: &Module,
+    before: Option<(&ProjectStructure, &Module)>,
+    structure: &ProjectStructure,
+) -> ModuleChange {
+    let mut details = Vec::new();
+    let mut item_changes = Vec::new();
+    if let Some((old, before)) = before {
+        if before.visibility != module.visibility {
+            details.push(format!("visibility {:?} → {:?}", before.visibility, module.visibility));
+        }
+        if before.module_type != module.module_type {
+            details.push(format!("type {:?} → {:?}", before.module_type, module.module_type));
+        }
+        let (old_path, new_path) = (relative_path(old, before), relative_path(structure, module));
+        if old_path != new_path {
+            details.push(format!("path {} → {}", old_path, new_path));
+        }
+        item_changes = compare_items(&before.items, &module.items);
+    }
+
+    let renamed = before.map(|(_, b)| b).filter(|b| b.id != module.id);
+    ModuleChange {
+        kind,
+        id: module.id.clone(),
+        name: module.name.clone(),
+        old_id: renamed.map(|b| b.id.clone()),
+        old_name: renamed.map(|b| b.name.clone()),
+        details,
+        item_changes,
+    }
+}
+
+fn compare_items(old: &[Item], new: &[Item]) -> Vec<ItemChange> {
+    let key = |i: &Item| (i.name.clone(), format!("{:?}", i.item_type));
+    let old_items: HashMap<(String, String), &Item> = old.iter().map(|i| (key(i), i)).collect();
+    let new_keys: HashSet<(String, String)> = new.iter().map(key).collect();
+
+    let mut changes = Vec::new();
+    for item in new {
+        let Some(before) = old_items.get(&key(item)) else {
+            changes.push(item_change(ChangeKind::Added, item, Vec::new()));
+            continue;
+        };
+        let mut details = Vec::new();
+        if before.visibility != item.visibility {
+            details.push(format!("visibility {:?} → {:?}", before.visibility, item.visibility));
+        }
+        if before.signature != item.signature {
+            details.push("signature changed".to_string());
+        }
+        if before.fields != item.fields {
+            let noun = match item.item_type {
+                ItemType::Enum => "variants",
+                ItemType::Trait => "members",
+                _ => "fields",
+            };
+            details.push(format!("{} changed", noun));
+        }
+        if before.bases != item.bases {
+            details.push("base classes changed".to_string());
+        }
+        if !details.is_empty() {
+            changes.push(item_change(ChangeKind::Changed, item, details));
+        }
+    }
+    for item in old.iter().filter(|i| !new_keys.contains(&key(i))) {
+        changes.push(item_change(ChangeKind::Removed, item, Vec::new()));
+    }
+    changes
+}
+
+fn item_change(kind: ChangeKind, item: &Item, details: Vec<String>) -> ItemChange {
+    ItemChange {
+        kind,
+        name: item.name.clone(),
+        item_type: item.item_type.clone(),
+        details,
+    }
+}
+
+fn change_icon(kind: &ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Added => "➕",
+        ChangeKind::Removed => "➖",
+        ChangeKind::Moved => "🚚",
+        ChangeKind::Renamed => "✏️",
+        ChangeKind::Changed => "🔧",
+    }
+}
+
+// `… and 3 more` once a list passes MARKDOWN_LIST_LIMIT
+fn push_limited(md: &mut String, lines: Vec<String>) {
+    let total = lines.len();
+    for line in lines.into_iter().take(MARKDOWN_LIST_LIMIT) {
+        md.push_str(&line);
+    }
+    if total > MARKDOWN_LIST_LIMIT {
+        md.push_str(&format!("- … and {} more\n", total - MARKDOWN_LIST_LIMIT));
+    }
+}
+
+// Summary for a pull request comment
+pub fn render_markdown(diff: &StructureDiff) -> String {
+    let mut md = String::from("## 🏗️ Architecture changes\n\n");
+    let unchanged = diff.modules.is_empty()
+        && diff.relationships.is_empty()
+        && diff.new_cycles.is_empty()
+        && diff.resolved_cycles.is_empty();
+    if unchanged {
+        md.push_str("No structural changes.\n");
+        return md;
+    }
+
+    md.push_str("| | Before | After | Change |\n|---|---:|---:|---:|\n");
+    for total in &diff.totals {
+        md.push_str(&format!(
+            "| {} | {} | {} | {:+} |\n",
+            total.name,
+            total.before,
+            total.after,
+            total.after as i64 - total.before as i64
+        ));
+    }
+
+    if !diff.new_cycles.is_empty() {
+        md.push_str("\n### ⚠️ New dependency cycles\n\n");
+        for cycle in &diff.new_cycles {
+            md.push_str(&format!("- `{}`\n", cycle_text(cycle)));
+        }
+    }
+    if !diff.resolved_cycles.is_empty() {
+        md.push_str("\n### ✅ Resolved dependency cycles\n\n");
+        for cycle in &diff.resolved_cycles {
+            md.push_str(&format!("- `{}`\n", cycle_text(cycle)));
+        }
+    }
+
+    if !diff.modules.is_empty() {
+        md.push_str("\n### Modules\n\n");
+        let lines = diff
+            .modules
+            .iter()
+            .map(|change| {
+                let mut line = match &change.old_name {
+                    Some(old_name) => format!(
+                        "- {} {:?} `{}` → `{}`",
+                        change_icon(&change.kind),
+                        change.kind,
+                        old_name,
+                        change.name
+                    ),
+                    None => format!("- {} {:?} `{}`", change_icon(&change.kind), change.kind, change.name),
+                };
+                if !change.details.is_empty() {
+                    line.push_str(&format!(" ({})", change.details.join(", ")));
+                }
+                line.push('\n');
+                if change.kind != ChangeKind::Added && change.kind != ChangeKind::Removed {
+                    for item in change.item_changes.iter().take(MARKDOWN_LIST_LIMIT) {
+                        line.push_str(&format!(
+                            "  - {} {} `{}`",
+                            format!("{:?}", item.kind).to_lowercase(),
+                            format!("{:?}", item.item_type).to_lowercase(),
+                            item.name
+                        ));
+                        if !item.details.is_empty() {
+                            line.push_str(&format!(": {}", item.details.join(", ")));
+                        }
+                        line.push('\n');
+                    }
+                }
+                line
+            })
+            .collect();
+        push_limited(&mut md, lines);
+    }
+
+    if !diff.relationships.is_empty() {
+        md.push_str("\n### Relationships\n\n");
+        let names: HashMap<&str, &str> = diff
+            .graph
+            .modules
+            .iter()
+            .map(|m| (m.id.as_str(), m.name.as_str()))
+            .collect();
+        let name = |id: &str| names.get(id).copied().unwrap_or(id).to_string();
+        let lines = diff
+            .relationships
+            .iter()
+            .map(|rel| {
+                format!(
+                    "- {} `{}` → `{}` ({})\n",
+                    change_icon(&rel.kind),
+                    name(&rel.from),
+                    name(&rel.to),
+                    relation_key(&rel.rel_type).to_lowercase()
+                )
+            })
+            .collect();
+        push_limited(&mut md, lines);
+    }
+
+    if !diff.metric_deltas.is_empty() {
+        md.push_str("\n### Metric changes\n\n");
+        md.push_str("| Module | Lines | Incoming | Outgoing | Complexity |\n|---|---:|---:|---:|---:|\n");
+        for delta in diff.metric_deltas.iter().take(MARKDOWN_LIST_LIMIT) {
+            md.push_str(&format!(
+                "| `{}` | {:+} | {:+} | {:+} | {:+} |\n",
+                delta.name, delta.lines_of_code, delta.incoming_deps, delta.outgoing_deps, delta.complexity_score
+            ));
+        }
+    }
+    md
+}
+
+fn cycle_text(cycle: &[String]) -> String {
+    let mut ids: Vec<&str> = cycle.iter().map(String::as_str).collect();
+    if let Some(first) = cycle.first() {
+        ids.push(first);
+    }
+    ids.join(" → ")
+}
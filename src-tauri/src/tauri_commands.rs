@@ -0,0 +1,111 @@
+use crate::models::*;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Tauri commands as seen from both sides of the bridge
+pub struct TauriCommands {
+    // command name -> Rust module defining the `#[tauri::command]`
+    pub defined: BTreeMap<String, String>,
+    // command names passed to `generate_handler!`
+    pub registered: Vec<String>,
+    // command name -> frontend modules calling `invoke`
+    pub invoked: BTreeMap<String, Vec<String>>,
+}
+
+impl TauriCommands {
+    pub fn collect(structure: &ProjectStructure) -> Self {
+        let mut commands = Self {
+            defined: BTreeMap::new(),
+            registered: Vec::new(),
+            invoked: BTreeMap::new(),
+        };
+
+        for module in &structure.modules {
+            for item in &module.items {
+                let Some(binding) = &item.binding else {
+                    continue;
+                };
+                match binding.kind {
+                    BindingKind::TauriCommand => {
+                        commands
+                            .defined
+                            .entry(binding.exported_name.clone())
+                            .or_insert_with(|| module.id.clone());
+                    }
+                    BindingKind::TauriHandler => {
+                        for name in &binding.exposes {
+                            if !commands.registered.contains(name) {
+                                commands.registered.push(name.clone());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            for name in &module.invokes {
+                commands.invoked.entry(name.clone()).or_default().push(module.id.clone());
+            }
+        }
+
+        commands
+    }
+
+    // Invoked from the frontend but missing from `generate_handler!`, which
+    // fails at runtime with "command not found"
+    pub fn unregistered(&self) -> Vec<String> {
+        self.invoked
+            .keys()
+            .filter(|name| !self.registered.contains(name))
+            .cloned()
+            .collect()
+    }
+
+    // Registered handlers no frontend module calls. Only meaningful once a
+    // frontend was analyzed at all.
+    pub fn uninvoked(&self, structure: &ProjectStructure) -> Vec<String> {
+        let has_frontend = structure
+            .modules
+            .iter()
+            .any(|m| matches!(m.language, Language::TypeScript | Language::JavaScript));
+        if !has_frontend {
+            return Vec::new();
+        }
+
+        let mut names: Vec<String> = self
+            .registered
+            .iter()
+            .filter(|name| !self.invoked.contains_key(*name))
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+// Links frontend `invoke` call sites to the Rust modules defining the commands
+pub fn link_commands(structure: &mut ProjectStructure, _root_path: &Path) {
+    let commands = TauriCommands::collect(structure);
+
+    // (frontend module, Rust module) -> command names
+    let mut edges: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for (name, callers) in &commands.invoked {
+        let Some(rust_module) = commands.defined.get(name) else {
+            continue;
+        };
+        for caller in callers {
+            edges
+                .entry((caller.clone(), rust_module.clone()))
+                .or_default()
+                .push(name.clone());
+        }
+    }
+
+    for ((from, to), names) in edges {
+        structure.relationships.push(Relationship {
+            from,
+            to,
+            rel_type: RelationType::Invokes,
+            label: Some(names.join(", ")),
+        });
+    }
+}
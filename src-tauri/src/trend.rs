@@ -0,0 +1,99 @@
+use crate::analyzer::{self, ProjectAnalyzer};
+use crate::git_history;
+use crate::models::*;
+use anyhow::Result;
+use std::path::Path;
+
+const CSV_HEADER: &str = "commit,date,author,summary,modules,relationships,lines_of_code,\
+average_coupling,largest_module,largest_module_lines,cycles,unused_modules,large_modules,\
+highly_coupled,unregistered_commands,uninvoked_commands";
+
+// Samples every `step`-th first-parent commit, `limit` in total, starting at
+// `revision` (HEAD by default), and returns their aggregates oldest first
+pub fn collect(
+    path: &Path,
+    revision: Option<&str>,
+    limit: usize,
+    step: usize,
+    options: &AnalysisOptions,
+) -> Result<Vec<TrendPoint>> {
+    let step = step.max(1);
+    let commits: Vec<CommitInfo> = git_history::list_commits(path, revision, limit * step)?
+        .into_iter()
+        .step_by(step)
+        .collect();
+
+    let mut points = Vec::new();
+    git_history::analyze_commits(path, &commits, options, |commit, structure| {
+        points.push(point(commit, structure))
+    })?;
+    Ok(points)
+}
+
+pub fn point(commit: &CommitInfo, structure: &ProjectStructure) -> TrendPoint {
+    let metrics = ProjectAnalyzer::new(structure.clone()).calculate_metrics();
+    let problems = analyzer::find_problems(structure);
+
+    let coupling: usize = metrics.values().map(|m| m.incoming_deps + m.outgoing_deps).sum();
+    let largest = structure
+        .modules
+        .iter()
+        .filter_map(|module| Some((module, metrics.get(&module.id)?.lines_of_code)))
+        .max_by_key(|(_, lines)| *lines);
+
+    TrendPoint {
+        commit: commit.clone(),
+        modules: structure.modules.len(),
+        relationships: structure.relationships.len(),
+        lines_of_code: metrics.values().map(|m| m.lines_of_code).sum(),
+        average_coupling: match metrics.len() {
+            0 => 0.0,
+            n => coupling as f64 / n as f64,
+        },
+        largest_module: largest.map(|(module, _)| module.name.clone()),
+        largest_module_lines: largest.map_or(0, |(_, lines)| lines),
+        cycles: problems.cycles.len(),
+        unused_modules: problems.unused_modules.len(),
+        large_modules: problems.large_modules.len(),
+        highly_coupled: problems.highly_coupled.len(),
+        unregistered_commands: problems.unregistered_commands.len(),
+        uninvoked_commands: problems.uninvoked_commands.len(),
+    }
+}
+
+// One row per commit, for spreadsheets and plotting tools
+pub fn to_csv(points: &[TrendPoint]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for p in points {
+        let row = [
+            p.commit.id.clone(),
+            p.commit.time.to_rfc3339(),
+            csv_field(&p.commit.author),
+            csv_field(&p.commit.summary),
+            p.modules.to_string(),
+            p.relationships.to_string(),
+            p.lines_of_code.to_string(),
+            format!("{:.2}", p.average_coupling),
+            csv_field(p.largest_module.as_deref().unwrap_or_default()),
+            p.largest_module_lines.to_string(),
+            p.cycles.to_string(),
+            p.unused_modules.to_string(),
+            p.large_modules.to_string(),
+            p.highly_coupled.to_string(),
+            p.unregistered_commands.to_string(),
+            p.uninvoked_commands.to_string(),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
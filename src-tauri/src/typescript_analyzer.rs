@@ -0,0 +1,564 @@
+use crate::language::{FileState, LanguageAnalyzer, ParsedFile};
+use crate::models::*;
+use crate::walker::SourceWalker;
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+// Import specifier -> file resolution order, as TypeScript's bundler resolution does
+const RESOLVE_EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+// tsconfig `compilerOptions.paths` entry, e.g. `@/*` -> [`src/*`]
+struct PathAlias {
+    pattern: String,
+    targets: Vec<String>,
+}
+
+pub struct TypeScriptAnalyzer {
+    root_path: PathBuf,
+    base_url: Option<PathBuf>,
+    aliases: Vec<PathAlias>,
+}
+
+impl TypeScriptAnalyzer {
+    pub fn new(root_path: PathBuf, _options: AnalysisOptions) -> Self {
+        Self {
+            root_path,
+            base_url: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn module_id(name: &str) -> String {
+        name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    // `src/components/index.tsx` -> `src/components`, `src/App.tsx` -> `src/App`
+    fn path_to_module_name(&self, path: &Path) -> String {
+        let relative = path.strip_prefix(&self.root_path).unwrap_or(path);
+        let mut name = relative.to_string_lossy().replace('\\', "/");
+        if let Some(stripped) = name.strip_suffix(".d.ts") {
+            name = stripped.to_string();
+        } else if let Some(pos) = name.rfind('.') {
+            if !name[pos..].contains('/') {
+                name.truncate(pos);
+            }
+        }
+        match name.strip_suffix("/index") {
+            Some(dir) => dir.to_string(),
+            None => name,
+        }
+    }
+
+    fn determine_module_type(&self, path: &Path) -> ModuleType {
+        let path_str = path.to_string_lossy();
+        let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        if path_str.contains("__tests__/") || file_name.contains(".test.") || file_name.contains(".spec.") {
+            ModuleType::Test
+        } else if path_str.contains("/examples/") {
+            ModuleType::Example
+        } else if file_name.starts_with("main.") {
+            ModuleType::Binary
+        } else if file_name.starts_with("index.") && path.parent() == Some(self.root_path.as_path()) {
+            ModuleType::Library
+        } else {
+            ModuleType::Module
+        }
+    }
+
+    fn load_tsconfig(&mut self) {
+        let mut visited = HashSet::new();
+        self.read_tsconfig(&self.root_path.join("tsconfig.json"), &mut visited);
+    }
+
+    // Reads compilerOptions from a tsconfig, following relative `extends` first
+    // so the extending file's settings win
+    fn read_tsconfig(&mut self, path: &Path, visited: &mut HashSet<PathBuf>) {
+        if !visited.insert(path.to_path_buf()) {
+            return;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            return;
+        };
+        let config: serde_json::Value = match serde_json::from_str(&strip_json_comments(&content)) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Failed to parse {}: {}", path.display(), e);
+                return;
+            }
+        };
+        let config_dir = path.parent().unwrap_or(&self.root_path).to_path_buf();
+
+        if let Some(extends) = config.get("extends").and_then(|v| v.as_str()) {
+            if extends.starts_with('.') {
+                let mut parent = config_dir.join(extends);
+                if parent.extension().is_none() {
+                    parent.set_extension("json");
+                }
+                self.read_tsconfig(&parent, visited);
+            }
+        }
+
+        let Some(options) = config.get("compilerOptions") else {
+            return;
+        };
+        if let Some(base_url) = options.get("baseUrl").and_then(|v| v.as_str()) {
+            self.base_url = Some(normalize(&config_dir.join(base_url)));
+        }
+        if let Some(paths) = options.get("paths").and_then(|v| v.as_object()) {
+            // `paths` are relative to baseUrl, or to the tsconfig itself without one
+            let base = self.base_url.clone().unwrap_or_else(|| config_dir.clone());
+            self.aliases = paths
+                .iter()
+                .map(|(pattern, targets)| PathAlias {
+                    pattern: pattern.clone(),
+                    targets: targets
+                        .as_array()
+                        .map(|t| {
+                            t.iter()
+                                .filter_map(|v| v.as_str())
+                                .map(|v| base.join(v).display().to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                })
+                .collect();
+        }
+    }
+
+    fn extract_imports(content: &str) -> Vec<String> {
+        static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+        let patterns = PATTERNS.get_or_init(|| {
+            [
+                r#"(?m)^\s*(?:import|export)\s[^;'"]*?\bfrom\s*['"]([^'"]+)['"]"#,
+                r#"(?m)^\s*import\s*['"]([^'"]+)['"]"#,
+                r#"\brequire\(\s*['"]([^'"]+)['"]\s*\)"#,
+                r#"\bimport\(\s*['"]([^'"]+)['"]\s*\)"#,
+            ]
+            .iter()
+            .map(|p| Regex::new(p).expect("valid import regex"))
+            .collect()
+        });
+
+        let mut imports = Vec::new();
+        for pattern in patterns {
+            for caps in pattern.captures_iter(content) {
+                let specifier = caps[1].to_string();
+                if !imports.contains(&specifier) {
+                    imports.push(specifier);
+                }
+            }
+        }
+        imports
+    }
+
+    fn extract_items(content: &str) -> Vec<Item> {
+        static DECLARATIONS: OnceLock<Vec<(Regex, ItemType)>> = OnceLock::new();
+        let declarations = DECLARATIONS.get_or_init(|| {
+            [
+                (r"(?m)^(export\s+(?:default\s+)?)?(?:declare\s+)?(?:async\s+)?function\s*\*?\s*(\w+)?", ItemType::Function),
+                (
+                    r"(?m)^(export\s+(?:default\s+)?)?(?:declare\s+)?(?:abstract\s+)?class\s+(\w+)(?:\s*<[^>{]*>)?(?:\s+extends\s+([\w.]+)(?:<[^>{]*>)?)?(?:\s+implements\s+([\w.,\s]+))?",
+                    ItemType::Struct,
+                ),
+                (r"(?m)^(export\s+)?(?:declare\s+)?interface\s+(\w+)", ItemType::Trait),
+                (r"(?m)^(export\s+)?(?:declare\s+)?type\s+(\w+)\s*(?:<[^=]*>)?\s*=", ItemType::Type),
+                (r"(?m)^(export\s+)?(?:declare\s+)?(?:const\s+)?enum\s+(\w+)", ItemType::Enum),
+                (
+                    r"(?m)^(export\s+)?(?:declare\s+)?(?:const|let|var)\s+(\w+)\s*(?::[^=]+)?=\s*(async\s+)?(function\b|\([^)]*\)\s*(?::[^=]+)?=>|\w+\s*=>)?",
+                    ItemType::Const,
+                ),
+            ]
+            .into_iter()
+            .map(|(p, t)| (Regex::new(p).expect("valid declaration regex"), t))
+            .collect()
+        });
+
+        let mut found: Vec<(usize, Item)> = Vec::new();
+        for (pattern, item_type) in declarations {
+            for caps in pattern.captures_iter(content) {
+                let exported = caps.get(1).is_some();
+                let name = caps
+                    .get(2)
+                    .map(|m| m.as_str().to_string())
+                    .unwrap_or_else(|| "default".to_string());
+
+                let mut item_type = item_type.clone();
+                let mut bases = Vec::new();
+                match item_type {
+                    ItemType::Struct => {
+                        bases.extend(caps.get(3).map(|m| m.as_str().to_string()));
+                        if let Some(implements) = caps.get(4) {
+                            bases.extend(
+                                implements
+                                    .as_str()
+                                    .split(',')
+                                    .map(|b| b.trim().to_string())
+                                    .filter(|b| !b.is_empty()),
+                            );
+                        }
+                    }
+                    // `const handler = async () => ...` is a function in all but name
+                    ItemType::Const if caps.get(4).is_some() => item_type = ItemType::Function,
+                    _ => {}
+                }
+
+                found.push((
+                    caps.get(0).map(|m| m.start()).unwrap_or(0),
+                    Item {
+                        name,
+                        item_type,
+                        visibility: if exported { Visibility::Public } else { Visibility::Private },
+                        bases,
+                        binding: None,
+                        span: None,
+                        docs: None,
+                        signature: None,
+                        fields: Vec::new(),
+                        effective_visibility: None,
+                        non_exhaustive: false,
+                    },
+                ));
+            }
+        }
+        found.sort_by_key(|(offset, _)| *offset);
+        let mut items: Vec<Item> = found.into_iter().map(|(_, item)| item).collect();
+
+        // `export { a, b as c }` and `export default App` publish earlier declarations
+        for name in Self::exported_names(content) {
+            if let Some(item) = items.iter_mut().find(|i| i.name == name) {
+                item.visibility = Visibility::Public;
+            }
+        }
+
+        items
+    }
+
+    fn exported_names(content: &str) -> Vec<String> {
+        static EXPORT_LIST: OnceLock<Regex> = OnceLock::new();
+        static EXPORT_DEFAULT: OnceLock<Regex> = OnceLock::new();
+        let export_list = EXPORT_LIST.get_or_init(|| {
+            Regex::new(r"(?m)^export\s*(?:type\s*)?\{([^}]*)\}\s*(from)?").expect("valid export list regex")
+        });
+        let export_default = EXPORT_DEFAULT
+            .get_or_init(|| Regex::new(r"(?m)^export\s+default\s+(\w+)\s*;?\s*$").expect("valid export default regex"));
+
+        let mut names = Vec::new();
+        for caps in export_list.captures_iter(content) {
+            if caps.get(2).is_some() {
+                continue;
+            }
+            for part in caps[1].split(',') {
+                if let Some(local) = part.split_whitespace().next() {
+                    names.push(local.to_string());
+                }
+            }
+        }
+        for caps in export_default.captures_iter(content) {
+            names.push(caps[1].to_string());
+        }
+        names
+    }
+
+    // `invoke('analyze_project', ...)` / `invoke<T>("...")` calls into the Tauri backend
+    fn extract_invokes(content: &str, imports: &[String]) -> Vec<String> {
+        if !imports.iter().any(|i| i.starts_with("@tauri-apps/api")) {
+            return Vec::new();
+        }
+
+        static INVOKE: OnceLock<Regex> = OnceLock::new();
+        let invoke = INVOKE.get_or_init(|| {
+            Regex::new(r#"\binvoke\s*(?:<[^>()]*>)?\(\s*['"`]([\w:|-]+)['"`]"#).expect("valid invoke regex")
+        });
+
+        let mut commands = Vec::new();
+        for caps in invoke.captures_iter(content) {
+            let command = caps[1].to_string();
+            // `plugin:fs|read_file` targets a plugin, not the app's own handlers
+            if !command.starts_with("plugin:") && !commands.contains(&command) {
+                commands.push(command);
+            }
+        }
+        commands
+    }
+
+    // Resolves an import specifier to a source file of this project, if it is one
+    fn resolve_import(&self, from_file: &Path, specifier: &str) -> Option<PathBuf> {
+        if specifier.starts_with('.') {
+            let dir = from_file.parent()?;
+            return Self::resolve_file(&normalize(&dir.join(specifier)));
+        }
+
+        for alias in &self.aliases {
+            let matched = match alias.pattern.split_once('*') {
+                Some((prefix, suffix)) => specifier
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(suffix)),
+                None => (specifier == alias.pattern).then_some(""),
+            };
+            let Some(wildcard) = matched else {
+                continue;
+            };
+            for target in &alias.targets {
+                let candidate = PathBuf::from(target.replacen('*', wildcard, 1));
+                if let Some(file) = Self::resolve_file(&normalize(&candidate)) {
+                    return Some(file);
+                }
+            }
+        }
+
+        let base_url = self.base_url.as_ref()?;
+        Self::resolve_file(&normalize(&base_url.join(specifier)))
+    }
+
+    fn resolve_file(candidate: &Path) -> Option<PathBuf> {
+        let has_source_extension = candidate
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| SOURCE_EXTENSIONS.contains(&e));
+        if has_source_extension && candidate.is_file() {
+            return Some(candidate.to_path_buf());
+        }
+
+        // ESM-style `./util.js` imports refer to `util.ts` in TypeScript sources
+        let stem = if has_source_extension {
+            candidate.with_extension("")
+        } else {
+            candidate.to_path_buf()
+        };
+        for ext in RESOLVE_EXTENSIONS {
+            let file = PathBuf::from(format!("{}.{}", stem.display(), ext));
+            if file.is_file() {
+                return Some(file);
+            }
+        }
+        for ext in RESOLVE_EXTENSIONS {
+            let index = stem.join(format!("index.{}", ext));
+            if index.is_file() {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    // `@tauri-apps/api/tauri` -> `@tauri-apps/api`, `node:fs` -> `fs`
+    fn package_name(specifier: &str) -> String {
+        let specifier = specifier.trim_start_matches("node:");
+        let mut parts = specifier.split('/');
+        match parts.next() {
+            Some(scope) if scope.starts_with('@') => match parts.next() {
+                Some(name) => format!("{}/{}", scope, name),
+                None => scope.to_string(),
+            },
+            Some(name) => name.to_string(),
+            None => specifier.to_string(),
+        }
+    }
+}
+
+impl LanguageAnalyzer for TypeScriptAnalyzer {
+    fn language(&self) -> Language {
+        Language::TypeScript
+    }
+
+    fn extensions(&self) -> &[&str] {
+        SOURCE_EXTENSIONS
+    }
+
+    fn detect(&self) -> bool {
+        self.root_path.join("package.json").exists() || self.root_path.join("tsconfig.json").exists()
+    }
+
+    fn parse_dependencies(&mut self) -> Result<Vec<Dependency>> {
+        self.load_tsconfig();
+
+        let path = self.root_path.join("package.json");
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&path).context("Failed to read package.json")?;
+        let package: serde_json::Value =
+            serde_json::from_str(&content).context("Failed to parse package.json")?;
+
+        let mut dependencies = Vec::new();
+        let sections = [
+            ("dependencies", DependencyType::Normal),
+            ("peerDependencies", DependencyType::Normal),
+            ("optionalDependencies", DependencyType::Normal),
+            ("devDependencies", DependencyType::Dev),
+        ];
+        for (section, dep_type) in sections {
+            let Some(deps) = package.get(section).and_then(|v| v.as_object()) else {
+                continue;
+            };
+            for (name, version) in deps {
+                dependencies.push(Dependency {
+                    name: name.clone(),
+                    version: version.as_str().unwrap_or("*").to_string(),
+                    dep_type: dep_type.clone(),
+                });
+            }
+        }
+
+        Ok(dependencies)
+    }
+
+    fn discover_files(&mut self, walker: &mut SourceWalker) -> Vec<PathBuf> {
+        walker.files(&self.root_path)
+    }
+
+    fn parse_file(&mut self, path: &Path) -> Result<ParsedFile> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+        let name = self.path_to_module_name(path);
+        let id = Self::module_id(&name);
+        let is_typescript = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.contains("ts"));
+
+        let imports = Self::extract_imports(&content);
+        let invokes = Self::extract_invokes(&content, &imports);
+
+        let mut relationships = Vec::new();
+        for specifier in imports {
+            let (to, label) = match self.resolve_import(path, &specifier) {
+                Some(file) => (Self::module_id(&self.path_to_module_name(&file)), None),
+                None if specifier.starts_with('.') => continue,
+                None => {
+                    let package = Self::package_name(&specifier);
+                    (Self::module_id(package.trim_start_matches('@')), Some(package))
+                }
+            };
+            if to != id && !relationships.iter().any(|r: &Relationship| r.to == to) {
+                relationships.push(Relationship {
+                    from: id.clone(),
+                    to,
+                    rel_type: RelationType::Uses,
+                    label,
+                });
+            }
+        }
+
+        let module = Module {
+            id,
+            name,
+            path: path.display().to_string(),
+            module_type: self.determine_module_type(path),
+            visibility: Visibility::Public,
+            items: Self::extract_items(&content),
+            entry_points: Vec::new(),
+            language: if is_typescript { Language::TypeScript } else { Language::JavaScript },
+            invokes,
+            impls: Vec::new(),
+            package: None,
+            docs: None,
+            reexports: Vec::new(),
+            lines_of_code: None,
+            churn: None,
+            owners: Vec::new(),
+            primary_authors: Vec::new(),
+            files: Vec::new(),
+        };
+
+        Ok(ParsedFile {
+            module,
+            relationships,
+            nested_modules: Vec::new(),
+            state: FileState::default(),
+        })
+    }
+
+    fn build_relationships(&mut self, modules: &[Module]) -> Vec<Relationship> {
+        // Directories with an index file declare the modules inside them
+        let mut relationships = Vec::new();
+        for module in modules {
+            let Some((parent_name, _)) = module.name.rsplit_once('/') else {
+                continue;
+            };
+            let parent_id = Self::module_id(parent_name);
+            if modules.iter().any(|m| m.id == parent_id) {
+                relationships.push(Relationship {
+                    from: parent_id,
+                    to: module.id.clone(),
+                    rel_type: RelationType::Declares,
+                    label: None,
+                });
+            }
+        }
+        relationships
+    }
+}
+
+// Lexically resolves `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+// tsconfig.json is JSONC: strip comments and trailing commas before parsing
+fn strip_json_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.next() {
+                    out.push(next);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    static TRAILING_COMMA: OnceLock<Regex> = OnceLock::new();
+    let trailing_comma = TRAILING_COMMA.get_or_init(|| Regex::new(r",(\s*[}\]])").expect("valid trailing comma regex"));
+    trailing_comma.replace_all(&out, "$1").into_owned()
+}
@@ -0,0 +1,237 @@
+use crate::models::*;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// Directories that never contain project sources, wherever they appear
+const DEFAULT_EXCLUDES: &[&str] = &[
+    "node_modules",
+    "__pycache__",
+    "site-packages",
+    ".venv",
+    "venv",
+    ".tox",
+    ".nox",
+    ".eggs",
+    ".mypy_cache",
+    ".pytest_cache",
+    ".ruff_cache",
+    ".idea",
+    ".vscode",
+];
+
+// Build output directories, only excluded at the project root so that
+// modules named e.g. `build` deeper in the tree are still analyzed
+const ROOT_EXCLUDES: &[&str] = &["target", "build", "dist"];
+
+const VCS_DIRS: &[&str] = &[".git", ".hg", ".svn"];
+
+// Walks source directories the way the project's own tooling would see them:
+// honoring .gitignore/.ignore files and skipping virtualenvs, caches and
+// dependency folders, while remembering what was skipped and why.
+pub struct SourceWalker {
+    root_path: PathBuf,
+    extensions: Vec<String>,
+    include_ignored: bool,
+    skipped: Vec<SkippedPath>,
+    // Set by has_files: stop at the first file and don't count skipped ones
+    first_only: bool,
+}
+
+impl SourceWalker {
+    pub fn new(root_path: &Path, extensions: &[&str], options: &AnalysisOptions) -> Self {
+        Self {
+            root_path: root_path.to_path_buf(),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            include_ignored: options.include_ignored,
+            skipped: Vec::new(),
+            first_only: false,
+        }
+    }
+
+    pub fn skipped(&self) -> Vec<SkippedPath> {
+        self.skipped.clone()
+    }
+
+    // Whether there is any source file below `dir`, without walking further
+    // than the first one
+    pub fn has_files(&mut self, dir: &Path) -> bool {
+        self.first_only = true;
+        let found = !self.files(dir).is_empty();
+        self.first_only = false;
+        found
+    }
+
+    // Returns all source files below `dir` in a stable order
+    pub fn files(&mut self, dir: &Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        if !dir.is_dir() {
+            return files;
+        }
+
+        let mut matchers = Vec::new();
+        if !self.include_ignored {
+            // Ignore files of the directories above `dir` apply as well
+            let mut ancestors: Vec<&Path> = dir
+                .ancestors()
+                .take_while(|p| p.starts_with(&self.root_path))
+                .skip(1)
+                .collect();
+            ancestors.reverse();
+            for ancestor in ancestors {
+                if let Some(matcher) = self.ignore_matcher(ancestor) {
+                    matchers.push(matcher);
+                }
+            }
+        }
+
+        self.walk_dir(dir, &mut matchers, &mut files);
+        files.sort();
+        files
+    }
+
+    fn walk_dir(&mut self, dir: &Path, matchers: &mut Vec<Gitignore>, files: &mut Vec<PathBuf>) {
+        let pushed = match self.include_ignored {
+            false => self.ignore_matcher(dir).map(|m| matchers.push(m)).is_some(),
+            true => false,
+        };
+
+        let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+            Err(e) => {
+                eprintln!("Failed to read directory {}: {}", dir.display(), e);
+                Vec::new()
+            }
+        };
+        entries.sort();
+
+        for path in entries {
+            if self.first_only && !files.is_empty() {
+                break;
+            }
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if metadata.file_type().is_symlink() {
+                continue;
+            }
+            let is_dir = metadata.is_dir();
+            let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+
+            if is_dir && VCS_DIRS.contains(&name) {
+                continue;
+            }
+
+            if let Some(reason) = self.skip_reason(&path, name, is_dir, matchers) {
+                if self.first_only {
+                    continue;
+                }
+                let file_count = self.count_source_files(&path, is_dir);
+                if file_count > 0 {
+                    self.skipped.push(SkippedPath {
+                        path: self.relative(&path),
+                        reason,
+                        file_count,
+                    });
+                }
+                continue;
+            }
+
+            if is_dir {
+                self.walk_dir(&path, matchers, files);
+            } else if self.has_source_extension(&path) {
+                files.push(path);
+            }
+        }
+
+        if pushed {
+            matchers.pop();
+        }
+    }
+
+    fn skip_reason(&self, path: &Path, name: &str, is_dir: bool, matchers: &[Gitignore]) -> Option<SkipReason> {
+        if self.include_ignored {
+            return None;
+        }
+
+        if is_dir {
+            if path.join("pyvenv.cfg").exists() {
+                return Some(SkipReason::Virtualenv);
+            }
+            let at_root = path.parent() == Some(self.root_path.as_path());
+            if DEFAULT_EXCLUDES.contains(&name)
+                || name.ends_with(".egg-info")
+                || (at_root && ROOT_EXCLUDES.contains(&name))
+            {
+                return Some(SkipReason::DefaultExclude);
+            }
+        }
+
+        // The deepest ignore file with an opinion wins, like in git
+        for matcher in matchers.iter().rev() {
+            let matched = matcher.matched(path, is_dir);
+            if matched.is_ignore() {
+                return Some(SkipReason::Ignored);
+            }
+            if matched.is_whitelist() {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    fn ignore_matcher(&self, dir: &Path) -> Option<Gitignore> {
+        let mut builder = GitignoreBuilder::new(dir);
+        let mut found = false;
+
+        let mut candidates = vec![dir.join(".gitignore"), dir.join(".ignore")];
+        if dir == self.root_path {
+            candidates.push(dir.join(".git").join("info").join("exclude"));
+        }
+
+        for file in candidates {
+            if file.is_file() {
+                if let Some(e) = builder.add(&file) {
+                    eprintln!("Invalid ignore file {}: {}", file.display(), e);
+                }
+                found = true;
+            }
+        }
+
+        if !found {
+            return None;
+        }
+        builder
+            .build()
+            .map_err(|e| eprintln!("Failed to build ignore rules for {}: {}", dir.display(), e))
+            .ok()
+    }
+
+    fn has_source_extension(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|s| s.to_str())
+            .map(|ext| self.extensions.iter().any(|e| e == ext))
+            .unwrap_or(false)
+    }
+
+    fn count_source_files(&self, path: &Path, is_dir: bool) -> usize {
+        if !is_dir {
+            return usize::from(self.has_source_extension(path));
+        }
+        WalkDir::new(path)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file() && self.has_source_extension(e.path()))
+            .count()
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root_path)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
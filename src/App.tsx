@@ -1,9 +1,10 @@
 import { useState } from 'react';
-import { open } from '@tauri-apps/api/dialog';
+import { open, save } from '@tauri-apps/api/dialog';
 import { invoke } from '@tauri-apps/api/tauri';
 import { FolderOpen, Loader2, AlertCircle } from 'lucide-react';
-import GraphView from './components/GraphView';
+import GraphView, { GraphChanges, ModuleMetrics, ownerColor } from './components/GraphView';
 import CodeViewer from './components/CodeViewer';
+import TrendView, { TrendPoint } from './components/TrendView';
 import './App.css';
 
 interface ProjectStructure {
@@ -11,6 +12,64 @@ interface ProjectStructure {
   modules: Module[];
   dependencies: Dependency[];
   relationships: Relationship[];
+  diagnostics?: AnalysisDiagnostics;
+  public_api?: PublicPath[];
+}
+
+interface PublicPath {
+  path: string;
+  item_type: string;
+  module_id: string;
+  name: string;
+}
+
+interface Snapshot {
+  format_version: number;
+  tool_version: string;
+  created_at: string;
+  git_commit: string | null;
+  structure: ProjectStructure;
+  problems: any;
+  metrics: Record<string, ModuleMetrics>;
+}
+
+interface OwnershipReport {
+  codeowners: string | null;
+  teams: { owner: string; modules: string[] }[];
+  boundary_edges: { from: string; to: string; from_owners: string[]; to_owners: string[] }[];
+  unowned_modules: string[];
+}
+
+interface CommitInfo {
+  id: string;
+  short_id: string;
+  summary: string;
+  author: string;
+  time: string;
+}
+
+interface StructureDiff {
+  modules: { kind: string; id: string; name: string; old_name: string | null }[];
+  relationships: { kind: string; from: string; to: string; rel_type: string }[];
+  new_cycles: string[][];
+  graph: ProjectStructure;
+  markdown: string;
+}
+
+interface AnalysisDiagnostics {
+  skipped: SkippedPath[];
+  manifest_errors?: ManifestError[];
+}
+
+interface ManifestError {
+  path: string;
+  message: string;
+}
+
+interface SkippedPath {
+  path: string;
+  reason: string;
+  file_count: number;
 }
 
 interface Module {
@@ -20,12 +79,29 @@ interface Module {
   module_type: string;
   visibility: string;
   items: Item[];
+  entry_points?: EntryPoint[];
+  language?: string;
+  invokes?: string[];
+  owners?: string[];
+  primary_authors?: { name: string; lines: number }[];
 }
 
 interface Item {
   name: string;
   item_type: string;
   visibility: string;
+  effective_visibility?: string | null;
+  bases?: string[];
+  span?: { start_line: number; end_line: number } | null;
+  docs?: string | null;
+}
+
+interface EntryPoint {
+  kind: string;
+  framework: string;
+  name: string;
+  handler: string | null;
+  methods: string[];
 }
 
 interface Dependency {
@@ -38,6 +114,7 @@ interface Relationship {
   from: string;
   to: string;
   rel_type: string;
+  label?: string | null;
 }
 
 function App() {
@@ -49,6 +126,16 @@ function App() {
   const [fileContent, setFileContent] = useState<string>('');
   const [search, setSearch] = useState('');
   const [problems, setProblems] = useState<any>(null);
+  const [snapshot, setSnapshot] = useState<Snapshot | null>(null);
+  const [diff, setDiff] = useState<StructureDiff | null>(null);
+  const [commits, setCommits] = useState<CommitInfo[]>([]);
+  const [comparing, setComparing] = useState(false);
+  const [trend, setTrend] = useState<TrendPoint[] | null>(null);
+  const [loadingTrend, setLoadingTrend] = useState(false);
+  const [metrics, setMetrics] = useState<Record<string, ModuleMetrics> | null>(null);
+  const [showHotspots, setShowHotspots] = useState(false);
+  const [loadingHotspots, setLoadingHotspots] = useState(false);
+  const [ownership, setOwnership] = useState<OwnershipReport | null>(null);
 
   const handleSelectFolder = async () => {
     try {
@@ -66,9 +153,19 @@ function App() {
         });
         
         setStructure(result);
+        setSnapshot(null);
+        setDiff(null);
+        setTrend(null);
+        setOwnership(null);
+        setShowHotspots(false);
         setLoading(false);
+        // Projects outside a git repository simply get no history
+        invoke<CommitInfo[]>('list_commits', { path: selected, limit: 30 })
+          .then(setCommits)
+          .catch(() => setCommits([]));
         const probs = await invoke('analyze_problems', { structure: result });
         setProblems(probs);
+        setMetrics(await invoke<Record<string, ModuleMetrics>>('calculate_metrics', { structure: result }));
       }
     } catch (err) {
       setError(err as string);
@@ -76,6 +173,183 @@ function App() {
     }
   };
 
+  const openSnapshot = async () => {
+    try {
+      const selected = await open({
+        multiple: false,
+        filters: [{ name: 'Snapshots', extensions: ['json'] }],
+      });
+      if (!selected || typeof selected !== 'string') return;
+
+      setError(null);
+      const result = await invoke<Snapshot>('load_snapshot', { path: selected });
+      setStructure(result.structure);
+      setProblems(result.problems);
+      setMetrics(result.metrics);
+      setSnapshot(result);
+      setDiff(null);
+      setCommits([]);
+      setTrend(null);
+      setOwnership(null);
+      setShowHotspots(false);
+      setSelectedModule(null);
+    } catch (err) {
+      setError(err as string);
+    }
+  };
+
+  const saveSnapshot = async () => {
+    if (!structure) return;
+
+    try {
+      const path = await save({
+        defaultPath: 'analysis.json',
+        filters: [{ name: 'Snapshots', extensions: ['json'] }],
+      });
+      if (!path) return;
+
+      await invoke<string>('save_snapshot', { structure, path });
+      alert(`✅ Snapshot saved!\n\nSaved to: ${path}`);
+    } catch (err) {
+      alert('❌ Failed to save snapshot: ' + err);
+    }
+  };
+
+  // Compares a saved snapshot (old) with the open project (new)
+  const compareWithSnapshot = async () => {
+    if (!structure) return;
+
+    try {
+      const selected = await open({
+        multiple: false,
+        filters: [{ name: 'Snapshots', extensions: ['json'] }],
+      });
+      if (!selected || typeof selected !== 'string') return;
+
+      const old = await invoke<Snapshot>('load_snapshot', { path: selected });
+      const result = await invoke<StructureDiff>('diff_structures', {
+        old: old.structure,
+        new: structure,
+      });
+      setDiff(result);
+    } catch (err) {
+      alert('❌ Failed to compare: ' + err);
+    }
+  };
+
+  // Compares the project at an earlier commit (old) with the open project (new)
+  const compareWithCommit = async (revision: string) => {
+    if (!structure || !revision) return;
+
+    setComparing(true);
+    try {
+      const old = await invoke<Snapshot>('analyze_revision', {
+        path: structure.root_path,
+        revision,
+      });
+      const result = await invoke<StructureDiff>('diff_structures', {
+        old: old.structure,
+        new: structure,
+      });
+      setDiff(result);
+    } catch (err) {
+      alert('❌ Failed to analyze revision: ' + err);
+    } finally {
+      setComparing(false);
+    }
+  };
+
+  const showTrend = async () => {
+    if (!structure) return;
+
+    setLoadingTrend(true);
+    try {
+      const points = await invoke<TrendPoint[]>('analyze_trend', {
+        path: structure.root_path,
+        limit: 20,
+      });
+      setTrend(points);
+    } catch (err) {
+      alert('❌ Failed to analyze history: ' + err);
+    } finally {
+      setLoadingTrend(false);
+    }
+  };
+
+  const exportTrend = async (format: 'csv' | 'json') => {
+    if (!trend) return;
+
+    try {
+      const path = await save({
+        defaultPath: `trend.${format}`,
+        filters: [{ name: format.toUpperCase(), extensions: [format] }],
+      });
+      if (!path) return;
+
+      const content = await invoke<string>('export_trend', { points: trend, format });
+      await invoke('save_file_content', { path, content });
+      alert(`✅ Trend exported!\n\nSaved to: ${path}`);
+    } catch (err) {
+      alert('❌ Failed to export trend: ' + err);
+    }
+  };
+
+  const toggleOwnership = async () => {
+    if (ownership) {
+      setOwnership(null);
+      return;
+    }
+    if (!structure) return;
+
+    try {
+      setOwnership(await invoke<OwnershipReport>('analyze_ownership', { structure }));
+      setShowHotspots(false);
+    } catch (err) {
+      alert('❌ Failed to analyze ownership: ' + err);
+    }
+  };
+
+  // Churn walks the git history, so it is only computed once hotspots are shown
+  const toggleHotspots = async () => {
+    if (showHotspots) {
+      setShowHotspots(false);
+      return;
+    }
+    if (!structure) return;
+
+    try {
+      if (!metrics || !Object.values(metrics).some(m => m.churn)) {
+        setLoadingHotspots(true);
+        const withChurn = await invoke<ProjectStructure>('link_churn', { structure });
+        setStructure(withChurn);
+        setMetrics(await invoke<Record<string, ModuleMetrics>>('calculate_metrics', { structure: withChurn }));
+      }
+      setShowHotspots(true);
+      setOwnership(null);
+    } catch (err) {
+      alert('❌ Failed to compute churn: ' + err);
+    } finally {
+      setLoadingHotspots(false);
+    }
+  };
+
+  const moduleName = (id: string) => structure?.modules.find(m => m.id === id)?.name ?? id;
+
+  // While comparing, the graph shows the merged old + new structure
+  const graphStructure = diff?.graph ?? structure;
+  const graphChanges: GraphChanges | null = diff ? {
+    modules: Object.fromEntries(diff.modules.map(m => [m.id, m.kind])),
+    relationships: Object.fromEntries(
+      diff.relationships.map(r => [`${r.from}|${r.to}|${r.rel_type}`, r.kind])
+    ),
+  } : null;
+
+  const copyDiffSummary = async () => {
+    if (!diff) return;
+    await navigator.clipboard.writeText(diff.markdown);
+    alert('✅ Markdown summary copied to the clipboard');
+  };
+
   const handleModuleClick = async (module: Module) => {
     setSelectedModule(module);
     setLoadingFile(true);
@@ -104,6 +378,82 @@ function App() {
     }
   };
 
+  const generateDocsFromTemplate = async () => {
+    if (!structure) return;
+
+    try {
+      const templatePath = await open({
+        multiple: false,
+        filters: [{ name: 'Templates', extensions: ['jinja', 'j2', 'md', 'txt'] }],
+      });
+      if (!templatePath || typeof templatePath !== 'string') return;
+
+      const path = await invoke<string>('generate_documentation', {
+        structure: structure,
+        templatePath,
+      });
+      alert(`✅ Documentation generated!\n\nSaved to: ${path}`);
+    } catch (err) {
+      alert('❌ Failed to generate docs: ' + err);
+    }
+  };
+
+  const generateHtmlDocs = async () => {
+    if (!structure) return;
+
+    try {
+      const outputDir = await open({
+        directory: true,
+        multiple: false,
+      });
+      if (!outputDir || typeof outputDir !== 'string') return;
+
+      const index = await invoke<string>('generate_html_docs', {
+        structure: structure,
+        outputDir,
+      });
+      alert(`✅ HTML documentation generated!\n\nOpen: ${index}`);
+    } catch (err) {
+      alert('❌ Failed to generate HTML docs: ' + err);
+    }
+  };
+
+  const exportGraph = async () => {
+    if (!structure) return;
+
+    try {
+      const path = await save({
+        defaultPath: 'project.dot',
+        filters: [
+          { name: 'Graphviz DOT', extensions: ['dot', 'gv'] },
+          { name: 'GraphML', extensions: ['graphml'] },
+          { name: 'GEXF', extensions: ['gexf'] },
+          { name: 'PlantUML components', extensions: ['puml'] },
+          { name: 'PlantUML classes', extensions: ['plantuml'] },
+          { name: 'Structurizr DSL', extensions: ['dsl'] },
+        ],
+      });
+      if (!path) return;
+
+      const formats: Record<string, string> = {
+        graphml: 'graphml',
+        gexf: 'gexf',
+        puml: 'plantuml-component',
+        plantuml: 'plantuml-class',
+        dsl: 'structurizr',
+      };
+      const extension = path.split('.').pop()?.toLowerCase() ?? '';
+      const format = formats[extension] ?? 'dot';
+      const content = await invoke<string>('export_graph', {
+        structure: structure,
+        options: { format },
+      });
+      await invoke('save_file_content', { path, content });
+    } catch (err) {
+      alert('❌ Failed to export graph: ' + err);
+    }
+  };
+
   return (
     <div className="app">
       <header className="header">
@@ -139,6 +489,59 @@ function App() {
             </>
           )}
         </button>
+        <button onClick={openSnapshot} className="btn-primary" disabled={loading}>
+          Open Snapshot
+        </button>
+        {structure && (
+          <button onClick={saveSnapshot} className="btn-primary">
+            Save Snapshot
+          </button>
+        )}
+        {structure && !diff && (
+          <button onClick={compareWithSnapshot} className="btn-primary">
+            Compare with Snapshot
+          </button>
+        )}
+        {structure && !snapshot && !diff && commits.length > 0 && (
+          <select
+            className="btn-primary"
+            value=""
+            disabled={comparing}
+            onChange={(e) => compareWithCommit(e.target.value)}
+          >
+            <option value="">{comparing ? 'Analyzing commit…' : 'Compare with Commit…'}</option>
+            {commits.map(c => (
+              <option key={c.id} value={c.id}>
+                {c.short_id} {new Date(c.time).toLocaleDateString()} {c.summary}
+              </option>
+            ))}
+          </select>
+        )}
+        {structure && ((!snapshot && commits.length > 0) || (metrics && Object.values(metrics).some(m => m.churn))) && (
+          <button onClick={toggleHotspots} className="btn-primary" disabled={loadingHotspots}>
+            {loadingHotspots ? 'Computing Churn…' : showHotspots ? 'Hide Hotspots' : 'Hotspots'}
+          </button>
+        )}
+        {structure && (
+          <button onClick={toggleOwnership} className="btn-primary">
+            {ownership ? 'Hide Ownership' : 'Ownership'}
+          </button>
+        )}
+        {structure && !snapshot && commits.length > 1 && !trend && (
+          <button onClick={showTrend} className="btn-primary" disabled={loadingTrend}>
+            {loadingTrend ? 'Analyzing History…' : 'Trend'}
+          </button>
+        )}
+        {diff && (
+          <>
+            <button onClick={copyDiffSummary} className="btn-primary">
+              Copy Diff Summary
+            </button>
+            <button onClick={() => setDiff(null)} className="btn-primary">
+              Close Diff
+            </button>
+          </>
+        )}
         {structure && (
           <button 
             onClick={generateDocs} 
@@ -159,6 +562,21 @@ function App() {
                 Generate Docs
               </button>
             )}
+        {structure && (
+          <button onClick={generateDocsFromTemplate} className="btn-primary">
+            Docs from Template
+          </button>
+        )}
+        {structure && (
+          <button onClick={generateHtmlDocs} className="btn-primary">
+            HTML Docs
+          </button>
+        )}
+        {structure && (
+          <button onClick={exportGraph} className="btn-primary">
+            Export Graph
+          </button>
+        )}
       </header>
 
       {error && (
@@ -173,27 +591,59 @@ function App() {
           <span>Modules: <strong>{structure.modules.length}</strong></span>
           <span>Dependencies: <strong>{structure.dependencies.length}</strong></span>
           <span>Public: <strong>{structure.modules.filter(m => m.visibility === 'public').length}</strong></span>
+          {structure.public_api && structure.public_api.length > 0 && (
+            <span title={structure.public_api.map(p => p.path).join('\n')}>
+              Public API: <strong>{structure.public_api.length}</strong>
+            </span>
+          )}
+          {snapshot && (
+            <span title={`Format ${snapshot.format_version}, saved by version ${snapshot.tool_version}`}>
+              Snapshot: <strong>{new Date(snapshot.created_at).toLocaleString()}</strong>
+              {snapshot.git_commit && <> @ <code>{snapshot.git_commit.slice(0, 8)}</code></>}
+            </span>
+          )}
+          {diff && (
+            <span title={diff.modules.map(m => `${m.kind}: ${m.old_name ? `${m.old_name} → ` : ''}${m.name}`).join('\n')}>
+              Diff: <strong>{diff.modules.length}</strong> module changes, <strong>{diff.relationships.length}</strong> edge changes
+              {diff.new_cycles.length > 0 && <>, <strong>{diff.new_cycles.length}</strong> new cycles</>}
+            </span>
+          )}
+          {structure.diagnostics && structure.diagnostics.skipped.length > 0 && (
+            <span title={structure.diagnostics.skipped.map(s => `${s.path} (${s.reason}): ${s.file_count}`).join('\n')}>
+              Skipped files: <strong>{structure.diagnostics.skipped.reduce((sum, s) => sum + s.file_count, 0)}</strong>
+            </span>
+          )}
+          {structure.diagnostics?.manifest_errors && structure.diagnostics.manifest_errors.length > 0 && (
+            <span title={structure.diagnostics.manifest_errors.map(e => `${e.path}: ${e.message}`).join('\n')}>
+              Unreadable manifests: <strong>{structure.diagnostics.manifest_errors.length}</strong>
+            </span>
+          )}
         </div>
 )}
 
       <main className="content">
-        {structure ? (
+        {structure && graphStructure ? (
           <>
             <div className="graph-panel">
               <GraphView
                structure={{
-                  ...structure,
-                  modules: structure.modules.filter(m =>
+                  ...graphStructure,
+                  modules: graphStructure.modules.filter(m =>
                     m.name.toLowerCase().includes(search.toLowerCase())
                   ),
-                  relationships: structure.relationships.filter(r =>
-                    structure.modules.some(m => 
+                  relationships: graphStructure.relationships.filter(r =>
+                    graphStructure.modules.some(m => 
                     (m.id === r.from || m.id === r.to) &&
                     m.name.toLowerCase().includes(search.toLowerCase())
                   )
                 )
             }}
             onModuleClick={handleModuleClick}              
+            changes={graphChanges}
+            hotspots={showHotspots && !diff ? metrics : null}
+            ownership={ownership && !diff ? {
+              boundaries: new Set(ownership.boundary_edges.map(e => `${e.from}|${e.to}`)),
+            } : null}
           />
             </div>
             
@@ -212,6 +662,36 @@ function App() {
                     </div>
                   )}
     
+                {selectedModule.entry_points && selectedModule.entry_points.length > 0 && (
+                  <div className="problem-section">
+                    <h3>Entry Points</h3>
+                    {selectedModule.entry_points.map((ep, i) => (
+                      <div key={i} className="problem-item">
+                        {ep.methods.length > 0 && `${ep.methods.join(',')} `}{ep.name}
+                        {ep.handler && ` → ${ep.handler}`} ({ep.framework})
+                      </div>
+                    ))}
+                  </div>
+                )}
+
+                {problems?.unregistered_commands?.length > 0 && (
+                  <div className="problem-section">
+                    <h3>Unregistered Commands</h3>
+                    {problems.unregistered_commands.map((cmd: string, i: number) => (
+                      <div key={i} className="problem-item error">{cmd}</div>
+                    ))}
+                  </div>
+                )}
+
+                {problems?.uninvoked_commands?.length > 0 && (
+                  <div className="problem-section">
+                    <h3>Commands Never Invoked</h3>
+                    {problems.uninvoked_commands.map((cmd: string, i: number) => (
+                      <div key={i} className="problem-item warning">{cmd}</div>
+                    ))}
+                  </div>
+                )}
+
                 {problems?.unused_modules?.length > 0 && (
                     <div className="problem-section">
                         <h3>Unused Modules</h3>
@@ -222,6 +702,53 @@ function App() {
                   )}
                 </div>
             )}
+
+            {ownership && (
+              <div className="problems-panel">
+                <h2>👥 Ownership</h2>
+                <p>
+                  {ownership.codeowners
+                    ? <>Owners from <code>{ownership.codeowners}</code></>
+                    : 'No CODEOWNERS file found'}
+                </p>
+
+                {ownership.teams.length > 0 && (
+                  <div className="problem-section">
+                    <h3>Teams</h3>
+                    {ownership.teams.map(team => (
+                      <div key={team.owner} className="problem-item" title={team.modules.join('\n')}>
+                        <span className="legend-color" style={{ background: ownerColor(team.owner), display: 'inline-block', marginRight: '0.5rem' }}></span>
+                        {team.owner}: {team.modules.length} modules
+                      </div>
+                    ))}
+                  </div>
+                )}
+
+                {ownership.boundary_edges.length > 0 && (
+                  <div className="problem-section">
+                    <h3>Dependencies Across Teams</h3>
+                    {ownership.boundary_edges.map(edge => (
+                      <div key={`${edge.from}|${edge.to}`} className="problem-item warning">
+                        {moduleName(edge.from)} ({edge.from_owners.join(', ')}) → {moduleName(edge.to)} ({edge.to_owners.join(', ')})
+                      </div>
+                    ))}
+                  </div>
+                )}
+
+                {ownership.unowned_modules.length > 0 && (
+                  <div className="problem-section">
+                    <h3>Modules Without an Owner</h3>
+                    {ownership.unowned_modules.map(name => (
+                      <div key={name} className="problem-item error">{name}</div>
+                    ))}
+                  </div>
+                )}
+              </div>
+            )}
+
+            {trend && (
+              <TrendView points={trend} onExport={exportTrend} onClose={() => setTrend(null)} />
+            )}
           </>
         ) : (
           <div className="empty-state">
@@ -10,12 +10,15 @@ interface Module {
   module_type: string;
   visibility: string;
   items: any[];
+  language?: string;
+  owners?: string[];
 }
 
 interface Relationship {
   from: string;
   to: string;
   rel_type: string;
+  label?: string | null;
 }
 
 interface ProjectStructure {
@@ -24,12 +27,65 @@ interface ProjectStructure {
   dependencies: any[];
 }
 
+// Change markers from a structure diff: module id -> change kind, and
+// `from|to|rel_type` -> change kind
+export interface GraphChanges {
+  modules: Record<string, string>;
+  relationships: Record<string, string>;
+}
+
+export interface ModuleMetrics {
+  lines_of_code: number;
+  complexity_score: number;
+  churn?: {
+    window_days: number;
+    commits: number;
+    lines_added: number;
+    lines_removed: number;
+    authors: number;
+  } | null;
+  hotspot_score?: number;
+}
+
 interface Props {
   structure: ProjectStructure;
   onModuleClick: (module: Module) => void;
+  changes?: GraphChanges | null;
+  // Colors modules by hotspot score when given
+  hotspots?: Record<string, ModuleMetrics> | null;
+  // Colors modules by owner and marks `from|to` edges crossing team
+  // boundaries when given
+  ownership?: { boundaries: Set<string> } | null;
 }
 
-const GraphView = ({ structure, onModuleClick }: Props) => {
+const ownerPalette = ['#e91e63', '#3f51b5', '#009688', '#ff9800', '#9c27b0', '#00bcd4', '#8bc34a', '#795548'];
+
+// A stable color per owner, so a team keeps its color between views
+export const ownerColor = (owner: string) => {
+  let hash = 0;
+  for (const c of owner) hash = (hash * 31 + c.charCodeAt(0)) >>> 0;
+  return ownerPalette[hash % ownerPalette.length];
+};
+
+// Grey for modules that didn't change, then yellow to red as the score grows
+const hotspotColor = (score: number) =>
+  score > 0 ? `hsl(${Math.round(50 * (1 - score))}, 90%, ${Math.round(45 + 10 * score)}%)` : '#424242';
+
+const hotspotTitle = (metrics: ModuleMetrics | undefined) => {
+  if (!metrics?.churn) return '';
+  const { churn } = metrics;
+  return `\nHotspot: ${(metrics.hotspot_score ?? 0).toFixed(2)} (${churn.commits} commits, +${churn.lines_added} -${churn.lines_removed} lines, ${churn.authors} authors in ${churn.window_days} days)`;
+};
+
+const changeColors: Record<string, string> = {
+  added: '#00e676',
+  removed: '#ff1744',
+  moved: '#40c4ff',
+  renamed: '#40c4ff',
+  changed: '#ffd740',
+};
+
+const GraphView = ({ structure, onModuleClick, changes, hotspots, ownership }: Props) => {
   const containerRef = useRef<HTMLDivElement>(null);
   const networkRef = useRef<Network | null>(null);
 
@@ -44,20 +100,39 @@ const GraphView = ({ structure, onModuleClick }: Props) => {
       return '#757575';
     };
 
+    const getEdgeColor = (relType: string) => {
+      if (relType === 'declares') return '#4caf50';
+      if (relType === 'inherits') return '#e91e63';
+      if (relType === 'binds') return '#ffc107';
+      if (relType === 'invokes') return '#9c27b0';
+      if (relType === 'reexports') return '#00897b';
+      return '#2196f3';
+    };
+
     const nodes = new DataSet(
-      structure.modules.map((module) => ({
+      structure.modules.map((module) => {
+    const change = changes?.modules[module.id];
+    const metrics = hotspots?.[module.id];
+    const owners = module.owners ?? [];
+    const background = hotspots
+      ? hotspotColor(metrics?.hotspot_score ?? 0)
+      : ownership
+        ? (owners.length > 0 ? ownerColor(owners[0]) : '#424242')
+        : getModuleColor(module.module_type, module.visibility);
+    return {
     id: module.id,
     label: module.name.split('::').pop() || module.name,
-    title: `${module.name}\nType: ${module.module_type}\nVisibility: ${module.visibility}\nItems: ${module.items.length}`,
+    title: `${module.name}\nLanguage: ${module.language ?? 'rust'}\nType: ${module.module_type}\nVisibility: ${module.visibility}\nItems: ${module.items.length}${change ? `\nChange: ${change}` : ''}${hotspotTitle(metrics)}${owners.length > 0 ? `\nOwners: ${owners.join(', ')}` : ''}`,
+    opacity: change === 'removed' ? 0.5 : 1,
     color: {
-      background: getModuleColor(module.module_type, module.visibility),
-      border: '#ffffff',
+      background,
+      border: change ? changeColors[change] : '#ffffff',
       highlight: {
         background: '#ff6b35',
         border: '#ffffff',
       },
       hover: {
-        background: getModuleColor(module.module_type, module.visibility),
+        background,
         border: '#ff6b35',
       }
     },
@@ -72,7 +147,8 @@ const GraphView = ({ structure, onModuleClick }: Props) => {
     },
     shape: 'box',
     margin: 12,
-    borderWidth: 3,
+    borderWidth: change ? 6 : 3,
+    shapeProperties: { borderDashes: ownership && owners.length === 0 ? [5, 5] : false },
     borderWidthSelected: 4,
     shadow: {
       enabled: true,
@@ -82,22 +158,28 @@ const GraphView = ({ structure, onModuleClick }: Props) => {
       y: 4
     },
     data: module,
-      }))
+    };
+      })
     );
 
     const edges = new DataSet(
-      structure.relationships.map((rel, idx) => ({
+      structure.relationships.map((rel, idx) => {
+        const change = changes?.relationships[`${rel.from}|${rel.to}|${rel.rel_type}`];
+        const crossing = rel.rel_type === 'uses' && ownership?.boundaries.has(`${rel.from}|${rel.to}`);
+        return {
         id: `edge-${idx}`,
         from: rel.from,
         to: rel.to,
         arrows: 'to',
         color: {
-          color: rel.rel_type === 'declares' ? '#4caf50' : '#2196f3',
+          color: change ? changeColors[change] : crossing ? '#ff1744' : getEdgeColor(rel.rel_type),
           highlight: '#ff6b35',
         },
-        dashes: rel.rel_type === 'uses',
-        title: rel.rel_type,
-      }))
+        dashes: rel.rel_type === 'uses' || change === 'removed',
+        width: change || crossing ? 5 : undefined,
+        title: (rel.label ? `${rel.rel_type}: ${rel.label}` : rel.rel_type) + (change ? ` (${change})` : '') + (crossing ? ' (crosses team boundary)' : ''),
+        };
+      })
     );
 
     const options = {
@@ -160,7 +242,7 @@ const GraphView = ({ structure, onModuleClick }: Props) => {
     return () => {
       network.destroy();
     };
-  }, [structure, onModuleClick]);
+  }, [structure, onModuleClick, changes, hotspots, ownership]);
 
   return (
     <div className="graph-container">
@@ -182,6 +264,24 @@ const GraphView = ({ structure, onModuleClick }: Props) => {
           <span className="legend-color" style={{ background: '#2196f3' }}></span>
           Example
         </div>
+        {hotspots && (
+          <div className="legend-item">
+            <span className="legend-color" style={{ background: `linear-gradient(90deg, ${hotspotColor(0.05)}, ${hotspotColor(1)})` }}></span>
+            Hotspot (frequently changed and complex)
+          </div>
+        )}
+        {ownership && (
+          <div className="legend-item">
+            <span className="legend-color" style={{ background: '#ff1744' }}></span>
+            Crosses team boundary
+          </div>
+        )}
+        {changes && ['added', 'removed', 'moved', 'changed'].map(kind => (
+          <div className="legend-item" key={kind}>
+            <span className="legend-color" style={{ border: `3px solid ${changeColors[kind]}` }}></span>
+            {kind[0].toUpperCase() + kind.slice(1)}
+          </div>
+        ))}
       </div>
     </div>
   );
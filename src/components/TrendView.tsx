@@ -0,0 +1,104 @@
+import './TrendView.css';
+
+export interface TrendPoint {
+  commit: {
+    id: string;
+    short_id: string;
+    summary: string;
+    author: string;
+    time: string;
+  };
+  modules: number;
+  relationships: number;
+  lines_of_code: number;
+  average_coupling: number;
+  largest_module: string | null;
+  largest_module_lines: number;
+  cycles: number;
+  unused_modules: number;
+  large_modules: number;
+  highly_coupled: number;
+}
+
+interface Props {
+  points: TrendPoint[];
+  onExport: (format: 'csv' | 'json') => void;
+  onClose: () => void;
+}
+
+const WIDTH = 340;
+const HEIGHT = 90;
+const PADDING = 6;
+
+const charts: { title: string; color: string; value: (p: TrendPoint) => number; detail?: (p: TrendPoint) => string }[] = [
+  { title: 'Modules', color: '#ff6b35', value: p => p.modules },
+  { title: 'Cycles', color: '#ff1744', value: p => p.cycles },
+  { title: 'Average coupling', color: '#40c4ff', value: p => Math.round(p.average_coupling * 100) / 100 },
+  {
+    title: 'Largest module (lines)',
+    color: '#ffd740',
+    value: p => p.largest_module_lines,
+    detail: p => p.largest_module ?? '',
+  },
+];
+
+// Simple SVG line chart, oldest commit on the left
+const Chart = ({ points, title, color, value, detail }: { points: TrendPoint[] } & typeof charts[number]) => {
+  const values = points.map(value);
+  const min = Math.min(...values);
+  const max = Math.max(...values);
+  const range = max - min || 1;
+  const x = (i: number) => PADDING + (i * (WIDTH - 2 * PADDING)) / Math.max(points.length - 1, 1);
+  const y = (v: number) => HEIGHT - PADDING - ((v - min) * (HEIGHT - 2 * PADDING)) / range;
+  const last = points[points.length - 1];
+
+  return (
+    <div className="trend-chart">
+      <h3>
+        {title}: <strong>{value(last)}</strong>
+        {detail && <span className="trend-detail"> {detail(last)}</span>}
+      </h3>
+      <svg width={WIDTH} height={HEIGHT}>
+        <polyline
+          fill="none"
+          stroke={color}
+          strokeWidth={2}
+          points={values.map((v, i) => `${x(i)},${y(v)}`).join(' ')}
+        />
+        {points.map((p, i) => (
+          <circle key={p.commit.id} cx={x(i)} cy={y(values[i])} r={3} fill={color}>
+            <title>
+              {`${p.commit.short_id} ${new Date(p.commit.time).toLocaleDateString()}\n${p.commit.summary}\n${title}: ${values[i]}${detail ? ` (${detail(p)})` : ''}`}
+            </title>
+          </circle>
+        ))}
+      </svg>
+      <div className="trend-range">min {min} · max {max}</div>
+    </div>
+  );
+};
+
+const TrendView = ({ points, onExport, onClose }: Props) => {
+  return (
+    <div className="problems-panel trend-panel">
+      <h2>📈 Trend over {points.length} commits</h2>
+      {points.length > 0 && (
+        <p className="trend-span">
+          {points[0].commit.short_id} → {points[points.length - 1].commit.short_id}
+        </p>
+      )}
+
+      {points.length > 0 && charts.map(chart => (
+        <Chart key={chart.title} points={points} {...chart} />
+      ))}
+
+      <div className="trend-actions">
+        <button onClick={() => onExport('csv')} className="btn-primary">Export CSV</button>
+        <button onClick={() => onExport('json')} className="btn-primary">Export JSON</button>
+        <button onClick={onClose} className="btn-primary">Close</button>
+      </div>
+    </div>
+  );
+};
+
+export default TrendView;